@@ -0,0 +1,39 @@
+//
+// date.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+
+/// 由毫秒时间戳计算年份 (北京时间)
+pub(crate) fn year_from_timestamp(ms: u64) -> u16 {
+    let days = (ms / 1000 + 8 * 3600) as i64 / 86400;
+    civil_from_days(days).0 as u16
+}
+
+/// 由 1970-01-01 起的天数计算 (年, 月, 日)
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_from_timestamp_uses_beijing_time() {
+        // 2020-01-01 00:00:00 +08:00
+        assert_eq!(year_from_timestamp(1577808000000), 2020);
+        // 2019-12-31 23:59:59 +08:00
+        assert_eq!(year_from_timestamp(1577807999000), 2019);
+    }
+}
@@ -0,0 +1,453 @@
+//
+// feed.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+use crate::date::civil_from_days;
+use crate::model::{Radio, RadioProgram, SongList, SongUrl};
+use crate::template::extension;
+use crate::{mime_type, MusicApi};
+use anyhow::{anyhow, Result};
+use futures_lite::future::block_on;
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::Duration,
+};
+
+/// 默认输出的节目数
+const FEED_LIMIT: u16 = 50;
+
+/// 订阅列表最多输出的电台数
+const SUBLIST_LIMIT: u16 = 1000;
+
+/// 连接读写超时
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 节目音频
+#[derive(Debug, Clone)]
+pub struct Enclosure {
+    /// 音频地址
+    pub url: String,
+    /// 文件大小
+    pub length: u64,
+    /// MIME 类型
+    pub mime: &'static str,
+}
+
+impl Enclosure {
+    /// url: 为空时使用歌曲 URL 本身
+    pub fn from_song_url(song_url: &SongUrl, url: Option<String>) -> Self {
+        Self {
+            url: url.unwrap_or_else(|| song_url.url.clone()),
+            length: song_url.size,
+            mime: mime_type(extension(song_url)),
+        }
+    }
+}
+
+/// 生成电台的 RSS 2.0 文档 (含 iTunes 扩展)
+/// radio: 电台详情
+/// programs: 节目列表
+/// enclosure: 节目音频，返回 None 的节目不输出
+pub fn radio_rss<F>(radio: &Radio, programs: &[RadioProgram], enclosure: F) -> String
+where
+    F: Fn(&RadioProgram) -> Option<Enclosure>,
+{
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n",
+    );
+    xml.push_str("<channel>\n");
+    let _ = writeln!(xml, "<title>{}</title>", escape(&radio.name));
+    let _ = writeln!(
+        xml,
+        "<link>https://music.163.com/#/djradio?id={}</link>",
+        radio.id
+    );
+    let _ = writeln!(xml, "<description>{}</description>", escape(&radio.desc));
+    xml.push_str("<language>zh-cn</language>\n");
+    let _ = writeln!(
+        xml,
+        "<itunes:author>{}</itunes:author>",
+        escape(&radio.dj_name)
+    );
+    let _ = writeln!(
+        xml,
+        "<itunes:summary>{}</itunes:summary>",
+        escape(&radio.desc)
+    );
+    if !radio.pic_url.is_empty() {
+        let _ = writeln!(xml, "<itunes:image href=\"{}\"/>", escape(&radio.pic_url));
+    }
+    if !radio.category.is_empty() {
+        let _ = writeln!(
+            xml,
+            "<itunes:category text=\"{}\"/>",
+            escape(&radio.category)
+        );
+    }
+    xml.push_str("<itunes:explicit>false</itunes:explicit>\n");
+    for program in programs {
+        let Some(enclosure) = enclosure(program) else {
+            continue;
+        };
+        xml.push_str("<item>\n");
+        let _ = writeln!(xml, "<title>{}</title>", escape(&program.name));
+        let _ = writeln!(
+            xml,
+            "<description>{}</description>",
+            escape(&program.description)
+        );
+        let _ = writeln!(
+            xml,
+            "<guid isPermaLink=\"false\">netease-program-{}</guid>",
+            program.id
+        );
+        let _ = writeln!(
+            xml,
+            "<link>https://music.163.com/#/program?id={}</link>",
+            program.id
+        );
+        let _ = writeln!(xml, "<pubDate>{}</pubDate>", rfc822(program.create_time));
+        let _ = writeln!(
+            xml,
+            "<enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>",
+            escape(&enclosure.url),
+            enclosure.length,
+            enclosure.mime
+        );
+        let _ = writeln!(
+            xml,
+            "<itunes:duration>{}</itunes:duration>",
+            program.duration / 1000
+        );
+        if program.serial_num > 0 {
+            let _ = writeln!(
+                xml,
+                "<itunes:episode>{}</itunes:episode>",
+                program.serial_num
+            );
+        }
+        if !program.cover_url.is_empty() {
+            let _ = writeln!(
+                xml,
+                "<itunes:image href=\"{}\"/>",
+                escape(&program.cover_url)
+            );
+        }
+        xml.push_str("</item>\n");
+    }
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// 生成电台订阅列表的 OPML 文档
+/// radios: 订阅的电台
+/// base: 电台 RSS 的地址前缀, 如 "http://127.0.0.1:8080"
+pub fn radios_opml(radios: &[SongList], base: &str) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<opml version=\"2.0\">\n");
+    xml.push_str("<head><title>网易云音乐电台订阅</title></head>\n<body>\n");
+    for radio in radios {
+        let _ = writeln!(
+            xml,
+            "<outline type=\"rss\" text=\"{0}\" title=\"{0}\" xmlUrl=\"{1}/radio/{2}.xml\" \
+             htmlUrl=\"https://music.163.com/#/djradio?id={2}\"/>",
+            escape(&radio.name),
+            escape(base),
+            radio.id
+        );
+    }
+    xml.push_str("</body>\n</opml>\n");
+    xml
+}
+
+/// XML 转义，并去除 XML 1.0 不允许的控制字符
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 毫秒时间戳转为 RFC 822 日期 (UTC)
+fn rfc822(ms: u64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let secs = ms / 1000;
+    let days = (secs / 86400) as i64;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} +0000",
+        WEEKDAYS[(days + 4).rem_euclid(7) as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// 本地播客服务
+/// GET /radios.opml: 已订阅电台的 OPML 列表
+/// GET /radio/{id}.xml: 电台 RSS，节目音频指向本服务
+/// GET /song/{id}: 获取最新的歌曲 URL 并重定向
+#[derive(Clone)]
+pub struct FeedServer {
+    api: MusicApi,
+    limit: u16,
+}
+
+impl FeedServer {
+    pub fn new(api: MusicApi) -> Self {
+        Self {
+            api,
+            limit: FEED_LIMIT,
+        }
+    }
+
+    /// 设置每个电台输出的节目数
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// 监听地址并阻塞处理请求，每个连接在独立线程中处理
+    /// addr: 监听地址, 如 "127.0.0.1:8080"
+    pub fn serve<A: ToSocketAddrs>(&self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        let local = listener.local_addr()?.to_string();
+        for stream in listener.incoming() {
+            // 单个连接出错不影响服务
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let server = self.clone();
+            let local = local.clone();
+            thread::spawn(move || server.handle(stream, &local));
+        }
+        Ok(())
+    }
+
+    fn handle(&self, mut stream: TcpStream, local: &str) -> Result<()> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_owned();
+        let target = parts.next().unwrap_or_default().to_owned();
+        let mut host = local.to_owned();
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("host") {
+                    host = value.trim().to_owned();
+                }
+            }
+        }
+
+        let response = match (method.as_str(), route(&target)) {
+            ("GET" | "HEAD", Some(Route::Index)) => {
+                match block_on(self.api.user_radio_sublist(0, SUBLIST_LIMIT)) {
+                    Ok(radios) => Response::ok(
+                        "text/x-opml; charset=utf-8",
+                        radios_opml(&radios, &format!("http://{}", host)),
+                    ),
+                    Err(err) => Response::error(502, &err.to_string()),
+                }
+            }
+            ("GET" | "HEAD", Some(Route::Radio(id))) => {
+                let base = format!("http://{}/song", host);
+                match block_on(self.api.radio_feed(id, self.limit, Some(&base))) {
+                    Ok(xml) => Response::ok("application/rss+xml; charset=utf-8", xml),
+                    Err(err) => Response::error(502, &err.to_string()),
+                }
+            }
+            ("GET" | "HEAD", Some(Route::Song(id))) => match block_on(self.song_url(id)) {
+                Ok(url) => Response::redirect(url),
+                Err(err) => Response::error(404, &err.to_string()),
+            },
+            ("GET" | "HEAD", None) => Response::error(404, "not found"),
+            _ => Response::error(405, "method not allowed"),
+        };
+        stream.write_all(&response.to_bytes(method == "HEAD"))?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    async fn song_url(&self, id: u64) -> Result<String> {
+        self.api
+            .songs_url_v1(&[id], crate::model::SongQuality::Extreme)
+            .await?
+            .into_iter()
+            .find(|u| u.id == id && !u.url.is_empty())
+            .map(|u| u.url)
+            .ok_or_else(|| anyhow!("song {} unavailable", id))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Index,
+    Radio(u64),
+    Song(u64),
+}
+
+fn route(target: &str) -> Option<Route> {
+    let path = target.split('?').next().unwrap_or_default();
+    if path == "/" || path == "/radios.opml" {
+        return Some(Route::Index);
+    }
+    if let Some(id) = path.strip_prefix("/radio/") {
+        return id.trim_end_matches(".xml").parse().ok().map(Route::Radio);
+    }
+    if let Some(id) = path.strip_prefix("/song/") {
+        // 播客客户端依赖扩展名识别格式，忽略之
+        let id = id.split('.').next().unwrap_or_default();
+        return id.parse().ok().map(Route::Song);
+    }
+    None
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn ok(content_type: &str, body: String) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type", content_type.to_owned())],
+            body,
+        }
+    }
+
+    fn redirect(location: String) -> Self {
+        Self {
+            status: 302,
+            headers: vec![("Location", location)],
+            body: String::new(),
+        }
+    }
+
+    fn error(status: u16, msg: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type", "text/plain; charset=utf-8".to_owned())],
+            body: msg.to_owned(),
+        }
+    }
+
+    fn to_bytes(&self, head: bool) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            302 => "Found",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Bad Gateway",
+        };
+        let mut out = format!("HTTP/1.1 {} {}\r\n", self.status, reason);
+        for (name, value) in &self.headers {
+            let _ = write!(out, "{}: {}\r\n", name, value);
+        }
+        let _ = write!(
+            out,
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        );
+        if !head {
+            out.push_str(&self.body);
+        }
+        out.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{to_radio_detail, to_radio_programs};
+
+    #[test]
+    fn radio_rss_escapes_and_dates() {
+        let radio = to_radio_detail(
+            r#"{"code":200,"data":{"id":7,"name":"Tom & Jerry","picUrl":"http://p?a=1&b=2",
+                "desc":"<简介>","dj":{"userId":1,"nickname":"主播"},"category":"情感"}}"#
+                .to_owned(),
+        )
+        .unwrap();
+        let programs = to_radio_programs(
+            r#"{"code":200,"programs":[
+                {"mainSong":{"id":11,"name":"a","duration":61000},"id":1,"name":"第2期","serialNum":2,
+                 "coverUrl":"","duration":61000,"createTime":1640995200000,"mainTrackId":11},
+                {"mainSong":{"id":12,"name":"b","duration":1000},"id":2,"name":"第1期","serialNum":1,
+                 "coverUrl":"","duration":1000,"createTime":1640908800000,"mainTrackId":12}]}"#
+                .to_owned(),
+        )
+        .unwrap();
+        let xml = radio_rss(&radio, &programs, |p| {
+            (p.song.id == 11).then(|| Enclosure {
+                url: "http://127.0.0.1:8080/song/11.mp3".to_owned(),
+                length: 1024,
+                mime: "audio/mpeg",
+            })
+        });
+        assert!(xml.contains("<title>Tom &amp; Jerry</title>"));
+        assert!(xml.contains("<itunes:image href=\"http://p?a=1&amp;b=2\"/>"));
+        assert!(xml.contains("<description>&lt;简介&gt;</description>"));
+        assert!(xml.contains("<pubDate>Sat, 01 Jan 2022 00:00:00 +0000</pubDate>"));
+        assert!(xml.contains("<itunes:duration>61</itunes:duration>"));
+        assert_eq!(xml.matches("<item>").count(), 1);
+    }
+
+    #[test]
+    fn route_requests() {
+        assert_eq!(route("/radio/794062371.xml"), Some(Route::Radio(794062371)));
+        assert_eq!(
+            route("/song/1901371647.mp3?t=1"),
+            Some(Route::Song(1901371647))
+        );
+        assert_eq!(route("/radios.opml"), Some(Route::Index));
+        assert_eq!(route("/"), Some(Route::Index));
+        assert_eq!(route("/favicon.ico"), None);
+    }
+
+    #[test]
+    fn radios_opml_links_feeds() {
+        let radios = vec![SongList {
+            id: 794062371,
+            name: "A & B".to_owned(),
+            cover_img_url: String::new(),
+            author: "主播".to_owned(),
+            special_type: 0,
+        }];
+        let xml = radios_opml(&radios, "http://127.0.0.1:8080");
+        assert!(xml.contains("text=\"A &amp; B\""));
+        assert!(xml.contains("xmlUrl=\"http://127.0.0.1:8080/radio/794062371.xml\""));
+        assert_eq!(xml.matches("<outline").count(), 1);
+    }
+}
@@ -0,0 +1,239 @@
+//
+// fm.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+use crate::model::{FmMode, PlayEnd, SongInfo, SongQuality, SongUrl};
+use crate::reporter::PlaybackReporter;
+use crate::MusicApi;
+use anyhow::Result;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// 默认预取数量
+const PREFETCH: usize = 2;
+
+/// FM 歌曲
+#[derive(Debug, Clone)]
+pub struct FmTrack {
+    /// 歌曲信息
+    pub song: SongInfo,
+    /// 预取的播放地址，获取失败或仅可试听时为 None
+    pub url: Option<SongUrl>,
+}
+
+/// 私人 FM 会话
+/// 缓存待播歌曲并在不足时补充，切歌/收藏/不喜欢时上报实际播放时长
+pub struct PersonalFm {
+    api: MusicApi,
+    mode: FmMode,
+    quality: SongQuality,
+    prefetch: usize,
+    queue: VecDeque<FmTrack>,
+    current: Option<FmTrack>,
+    played: Duration,
+    resumed: Option<Instant>,
+    reporter: PlaybackReporter,
+}
+
+impl PersonalFm {
+    pub fn new(api: MusicApi) -> Self {
+        Self {
+            reporter: PlaybackReporter::new(api.clone()),
+            api,
+            mode: FmMode::default(),
+            quality: SongQuality::Extreme,
+            prefetch: PREFETCH,
+            queue: VecDeque::new(),
+            current: None,
+            played: Duration::ZERO,
+            resumed: None,
+        }
+    }
+
+    /// 设置 FM 模式
+    pub fn mode(mut self, mode: FmMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 设置预取地址的音质
+    pub fn quality(mut self, quality: SongQuality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// 设置切歌时待播歌曲不多于多少首时补充
+    /// prefetch: 为 0 时仅在队列为空时补充
+    pub fn prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// 切换 FM 模式，清空待播歌曲
+    pub fn set_mode(&mut self, mode: FmMode) {
+        self.mode = mode;
+        self.queue.clear();
+    }
+
+    /// 当前歌曲
+    pub fn current(&self) -> Option<&FmTrack> {
+        self.current.as_ref()
+    }
+
+    /// 待播歌曲
+    pub fn upcoming(&self) -> impl Iterator<Item = &FmTrack> {
+        self.queue.iter()
+    }
+
+    /// 暂停计时
+    pub fn pause(&mut self) {
+        if let Some(resumed) = self.resumed.take() {
+            self.played += resumed.elapsed();
+        }
+    }
+
+    /// 继续计时
+    pub fn resume(&mut self) {
+        if self.current.is_some() && self.resumed.is_none() {
+            self.resumed = Some(Instant::now());
+        }
+    }
+
+    /// 当前歌曲已播放时长(秒)，不含暂停时间
+    pub fn played_seconds(&self) -> u64 {
+        (self.played + self.resumed.map_or(Duration::ZERO, |r| r.elapsed())).as_secs()
+    }
+
+    /// 当前歌曲播放完毕，切换到下一首
+    pub async fn next(&mut self) -> Result<Option<&FmTrack>> {
+        self.advance(PlayEnd::PlayEnd).await
+    }
+
+    /// 跳过当前歌曲
+    pub async fn skip(&mut self) -> Result<Option<&FmTrack>> {
+        self.advance(PlayEnd::Ui).await
+    }
+
+    /// 收藏/取消收藏当前歌曲
+    /// like: true 收藏，false 取消
+    pub async fn like(&self, like: bool) -> bool {
+        match &self.current {
+            Some(track) => {
+                self.api
+                    .fm_like(like, track.song.id, self.played_seconds())
+                    .await
+            }
+            None => false,
+        }
+    }
+
+    /// 不喜欢当前歌曲并跳过
+    pub async fn trash(&mut self) -> Result<Option<&FmTrack>> {
+        if let Some(track) = &self.current {
+            self.api
+                .fm_dislike(track.song.id, self.played_seconds())
+                .await;
+        }
+        self.advance(PlayEnd::Ui).await
+    }
+
+    async fn advance(&mut self, end: PlayEnd) -> Result<Option<&FmTrack>> {
+        if self.current.is_some() {
+            self.reporter.end(self.played_seconds(), end);
+        }
+        if self.queue.len() <= self.prefetch {
+            if let Err(err) = self.refill().await {
+                if self.queue.is_empty() {
+                    return Err(err);
+                }
+            }
+        }
+        self.current = self.queue.pop_front();
+        self.played = Duration::ZERO;
+        self.resumed = None;
+        if let Some(track) = &self.current {
+            self.reporter.start(track.song.id, 0);
+            self.resumed = Some(Instant::now());
+        }
+        // 上报失败的事件保留到下次切歌时重试
+        let _ = self.reporter.flush().await;
+        Ok(self.current.as_ref())
+    }
+
+    async fn refill(&mut self) -> Result<()> {
+        let songs = self.api.personal_fm_mode(self.mode).await?;
+        let ids: Vec<u64> = songs.iter().map(|s| s.id).collect();
+        // 地址获取失败时由调用方在播放前重新获取
+        let urls = self
+            .api
+            .songs_url_v1(&ids, self.quality)
+            .await
+            .unwrap_or_default();
+        self.enqueue(songs, urls);
+        Ok(())
+    }
+
+    fn enqueue(&mut self, songs: Vec<SongInfo>, urls: Vec<SongUrl>) {
+        for song in songs {
+            let queued = self
+                .current
+                .iter()
+                .chain(self.queue.iter())
+                .any(|t| t.song.id == song.id);
+            if queued {
+                continue;
+            }
+            let url = urls
+                .iter()
+                .find(|u| u.id == song.id && u.playable())
+                .cloned();
+            self.queue.push_back(FmTrack { song, url });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SongUnavailable;
+
+    fn song(id: u64) -> SongInfo {
+        SongInfo {
+            id,
+            name: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn enqueue_skips_duplicates_and_pauses() {
+        let mut fm = PersonalFm::new(MusicApi::default());
+        let url = SongUrl {
+            id: 2,
+            url: "http://m701.music.126.net/a.mp3".to_owned(),
+            ..Default::default()
+        };
+        fm.enqueue(vec![song(1), song(2)], vec![url]);
+        fm.current = fm.queue.pop_front();
+        let trial = SongUrl {
+            id: 3,
+            url: "http://m701.music.126.net/b.mp3".to_owned(),
+            unavailable: Some(SongUnavailable::TrialOnly),
+            ..Default::default()
+        };
+        fm.enqueue(vec![song(1), song(2), song(3)], vec![trial]);
+        let ids: Vec<u64> = fm.upcoming().map(|t| t.song.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert!(fm.queue[0].url.is_some());
+        assert!(fm.queue[1].url.is_none());
+
+        fm.played = Duration::from_secs(30);
+        fm.resume();
+        fm.pause();
+        fm.pause();
+        assert_eq!(fm.played_seconds(), 30);
+    }
+}
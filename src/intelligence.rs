@@ -0,0 +1,141 @@
+//
+// intelligence.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+use crate::model::IntelligenceTrack;
+use crate::MusicApi;
+use anyhow::{anyhow, Result};
+use std::collections::{HashSet, VecDeque};
+
+/// 每次请求的默认歌曲数
+const COUNT: u16 = 10;
+/// 返回歌曲全部重复时的最多重新请求次数
+const REFETCH: usize = 3;
+
+/// 心动模式会话
+/// 以歌单内一首歌为起点，待播歌曲用完时以最后一首继续请求
+pub struct IntelligenceSession {
+    api: MusicApi,
+    song_id: u64,
+    playlist_id: u64,
+    count: u16,
+    queue: VecDeque<IntelligenceTrack>,
+    current: Option<IntelligenceTrack>,
+    seen: HashSet<u64>,
+    last_id: u64,
+}
+
+impl IntelligenceSession {
+    /// song_id: 起始歌曲ID
+    /// playlist_id: 歌单ID
+    pub fn new(api: MusicApi, song_id: u64, playlist_id: u64) -> Self {
+        Self {
+            api,
+            song_id,
+            playlist_id,
+            count: COUNT,
+            queue: VecDeque::new(),
+            current: None,
+            seen: HashSet::new(),
+            last_id: song_id,
+        }
+    }
+
+    /// 从歌单中随机选取起始歌曲
+    /// playlist_id: 歌单ID
+    pub async fn random(api: MusicApi, playlist_id: u64) -> Result<Self> {
+        let detail = api.song_list_detail(playlist_id).await?;
+        if detail.songs.is_empty() {
+            return Err(anyhow!("playlist {} is empty", playlist_id));
+        }
+        let song_id = detail.songs[rand::random_range(0..detail.songs.len())].id;
+        Ok(Self::new(api, song_id, playlist_id))
+    }
+
+    /// 设置每次请求的歌曲数
+    pub fn count(mut self, count: u16) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// 起始歌曲ID
+    pub fn song_id(&self) -> u64 {
+        self.song_id
+    }
+
+    /// 当前歌曲
+    pub fn current(&self) -> Option<&IntelligenceTrack> {
+        self.current.as_ref()
+    }
+
+    /// 待播歌曲
+    pub fn upcoming(&self) -> impl Iterator<Item = &IntelligenceTrack> {
+        self.queue.iter()
+    }
+
+    /// 切换到下一首，待播歌曲用完时继续请求
+    pub async fn next(&mut self) -> Result<Option<&IntelligenceTrack>> {
+        for _ in 0..REFETCH {
+            if !self.queue.is_empty() {
+                break;
+            }
+            let tracks = self
+                .api
+                .playmode_intelligence(self.song_id, self.playlist_id, self.last_id, self.count)
+                .await?;
+            if tracks.is_empty() {
+                break;
+            }
+            self.enqueue(tracks);
+        }
+        self.current = self.queue.pop_front();
+        Ok(self.current.as_ref())
+    }
+
+    /// 以最后一首入队歌曲作为下次请求起点，全部重复时以返回的最后一首继续
+    fn enqueue(&mut self, tracks: Vec<IntelligenceTrack>) {
+        let fallback = tracks.last().map(|t| t.song.id);
+        let mut last = None;
+        for track in tracks {
+            if self.seen.insert(track.song.id) {
+                last = Some(track.song.id);
+                self.queue.push_back(track);
+            }
+        }
+        if let Some(id) = last.or(fallback) {
+            self.last_id = id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::to_intelligence_tracks;
+
+    #[test]
+    fn enqueue_continues_from_last_track() {
+        let json = r#"{"code":200,"data":[
+            {"id":1,"recommended":false,"songInfo":{"id":1,"name":"a","ar":[],"al":{"id":1,"name":"x"},"dt":1}},
+            {"id":2,"recommended":true,"alg":"hb","songInfo":{"id":2,"name":"b","ar":[],"al":{"id":1,"name":"x"},"dt":1}},
+            {"id":1,"recommended":false,"songInfo":{"id":1,"name":"a","ar":[],"al":{"id":1,"name":"x"},"dt":1}}]}"#;
+        let mut session = IntelligenceSession::new(MusicApi::default(), 1, 100);
+        session.enqueue(to_intelligence_tracks(json.to_owned()).unwrap());
+        let ids: Vec<u64> = session.upcoming().map(|t| t.song.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(session.last_id, 2);
+    }
+
+    #[test]
+    fn enqueue_advances_past_all_duplicates() {
+        let json = r#"{"code":200,"data":[
+            {"id":1,"recommended":false,"songInfo":{"id":1,"name":"a","ar":[],"al":{"id":1,"name":"x"},"dt":1}},
+            {"id":3,"recommended":true,"songInfo":{"id":3,"name":"c","ar":[],"al":{"id":1,"name":"x"},"dt":1}}]}"#;
+        let mut session = IntelligenceSession::new(MusicApi::default(), 1, 100);
+        session.seen.extend([1, 3]);
+        session.enqueue(to_intelligence_tracks(json.to_owned()).unwrap());
+        assert_eq!(session.upcoming().count(), 0);
+        assert_eq!(session.last_id, 3);
+    }
+}
@@ -3,21 +3,37 @@
 // Copyright (C) 2019 gmg137 <gmg137@live.com>
 // Distributed under terms of the GPLv3 license.
 //
+mod date;
 mod encrypt;
+mod feed;
+mod fm;
+mod intelligence;
 pub(crate) mod model;
+mod reporter;
+mod tag;
+mod template;
 use anyhow::{anyhow, Result};
 use encrypt::Crypto;
+pub use feed::{radio_rss, radios_opml, Enclosure, FeedServer};
+pub use fm::{FmTrack, PersonalFm};
+pub use intelligence::IntelligenceSession;
 pub use isahc::cookies::{CookieBuilder, CookieJar};
 use isahc::{prelude::*, *};
 use lazy_static::lazy_static;
 pub use model::*;
+use openssl::hash::{Hasher, MessageDigest};
 use regex::Regex;
+pub use reporter::PlaybackReporter;
 use std::{
     collections::HashMap,
-    path::PathBuf,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
     sync::OnceLock,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
+pub use tag::SongTag;
+pub use template::{unique_path, FileNameTemplate};
 
 lazy_static! {
     static ref _CSRF: Regex = Regex::new(r"_csrf=(?P<csrf>[^(;|$)]+)").unwrap();
@@ -27,6 +43,13 @@ static BASE_URL: &str = "https://music.163.com";
 
 const TIMEOUT: u64 = 100;
 
+/// 云盘上传使用的 NOS 负载均衡地址
+const NOS_LBS_URL: &str =
+    "https://wanproxy.127.net/lbs?version=1.0&bucketname=jd-musicrep-privatecloud-audio-public";
+
+/// 云盘上传分片大小
+const NOS_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
 const LINUX_USER_AGNET: &str =
     "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/60.0.3112.90 Safari/537.36";
 
@@ -379,6 +402,63 @@ impl MusicApi {
         to_login_info(result)
     }
 
+    /// 账号信息 (用户信息、VIP 状态、等级及收藏数量)
+    #[allow(unused)]
+    pub async fn account_info(&self) -> Result<AccountInfo> {
+        let mut profile = to_user_detail(
+            self.request(
+                Method::Post,
+                "/api/nuser/account/get",
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?,
+        )?;
+        let vip = to_vip_status(
+            self.request(
+                Method::Post,
+                "/weapi/music-vip-membership/front/vip/info",
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?,
+        )?;
+        let level = to_user_level(
+            self.request(
+                Method::Post,
+                "/weapi/user/level",
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?,
+        )?;
+        let sub_count = to_sub_count(
+            self.request(
+                Method::Post,
+                "/weapi/subcount",
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?,
+        )?;
+        profile.level = level.level;
+        profile.playlist_count = sub_count.created_playlist;
+        Ok(AccountInfo {
+            profile,
+            vip,
+            level,
+            sub_count,
+        })
+    }
+
     /// 退出
     #[allow(unused)]
     pub async fn logout(&self) {
@@ -406,6 +486,95 @@ impl MusicApi {
         to_msg(result)
     }
 
+    /// 用户详情
+    /// uid: 用户id
+    #[allow(unused)]
+    pub async fn user_detail(&self, uid: u64) -> Result<UserProfile> {
+        let path = format!("/weapi/v1/user/detail/{}", uid);
+        let result = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_user_detail(result)
+    }
+
+    /// 用户关注列表
+    /// uid: 用户id
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn user_follows(&self, uid: u64, offset: u16, limit: u16) -> Result<UserPage> {
+        let path = format!("/weapi/user/getfollows/{}", uid);
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        params.insert("order", "true");
+        let result = self
+            .request(Method::Post, &path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_user_list(result, Parse::Follows)
+    }
+
+    /// 用户粉丝列表
+    /// uid: 用户id
+    /// lasttime: 上一页返回的 lasttime，首页传 -1
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn user_followeds(&self, uid: u64, lasttime: i64, limit: u16) -> Result<UserPage> {
+        let path = format!("/weapi/user/getfolloweds/{}", uid);
+        let mut params = HashMap::new();
+        let uid = uid.to_string();
+        let lasttime = lasttime.to_string();
+        let limit = limit.to_string();
+        params.insert("userId", uid.as_str());
+        params.insert("time", lasttime.as_str());
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, &path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_user_list(result, Parse::Followeds)
+    }
+
+    /// 关注/取消关注用户
+    /// follow: true 关注，false 取消
+    /// uid: 用户id
+    #[allow(unused)]
+    pub async fn follow(&self, follow: bool, uid: u64) -> bool {
+        let path = if follow {
+            format!("/weapi/user/follow/{}", uid)
+        } else {
+            format!("/weapi/user/delfollow/{}", uid)
+        };
+        if let Ok(result) = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await
+        {
+            return to_msg(result)
+                .unwrap_or(Msg {
+                    code: 0,
+                    msg: "".to_owned(),
+                })
+                .code
+                .eq(&200);
+        }
+        false
+    }
+
     /// 用户喜欢音乐id列表
     /// uid: 用户id
     #[allow(unused)]
@@ -440,6 +609,74 @@ impl MusicApi {
         to_song_list(result, Parse::Usl)
     }
 
+    /// 用户听歌排行
+    /// uid: 用户id
+    /// range: 时间范围
+    #[allow(unused)]
+    pub async fn user_play_record(
+        &self,
+        uid: u64,
+        range: PlayRecordRange,
+    ) -> Result<Vec<PlayRecord>> {
+        let path = "/weapi/v1/play/record";
+        let mut params = HashMap::new();
+        let uid = uid.to_string();
+        params.insert("uid", uid.as_str());
+        params.insert(
+            "type",
+            match range {
+                PlayRecordRange::Week => "1",
+                PlayRecordRange::All => "0",
+            },
+        );
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_play_record(result, range)
+    }
+
+    /// 最近播放歌曲
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn recent_songs(&self, limit: u16) -> Result<Vec<RecentPlayed<SongInfo>>> {
+        let path = "/api/play-record/song/list";
+        let mut params = HashMap::new();
+        let limit = limit.to_string();
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_recent_songs(result)
+    }
+
+    /// 最近播放歌单
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn recent_playlists(&self, limit: u16) -> Result<Vec<RecentPlayed<SongList>>> {
+        let path = "/api/play-record/playlist/list";
+        let mut params = HashMap::new();
+        let limit = limit.to_string();
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_recent_song_list(result, Parse::Usl)
+    }
+
+    /// 最近播放专辑
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn recent_albums(&self, limit: u16) -> Result<Vec<RecentPlayed<SongList>>> {
+        let path = "/api/play-record/album/list";
+        let mut params = HashMap::new();
+        let limit = limit.to_string();
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_recent_song_list(result, Parse::LikeAlbum)
+    }
+
     /// 用户收藏专辑列表
     /// offset: 列表起点号
     /// limit: 列表长度
@@ -469,13 +706,262 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        to_song_info(result, Parse::Ucd)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Ucd)?)
+            .await)
+    }
+
+    /// 云盘歌曲及容量信息
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn cloud_disk(&self, offset: u16, limit: u16) -> Result<CloudDisk> {
+        let path = "/weapi/v1/cloud/get";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_cloud_disk(result)
+    }
+
+    /// 删除云盘歌曲
+    /// ids: 云盘歌曲 id 列表
+    #[allow(unused)]
+    pub async fn cloud_delete(&self, ids: &[u64]) -> bool {
+        let path = "/weapi/cloud/del";
+        let mut params = HashMap::new();
+        let ids = serde_json::to_string(ids).unwrap_or_default();
+        params.insert("songIds", ids.as_str());
+        if let Ok(result) = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await
+        {
+            return to_msg(result)
+                .unwrap_or(Msg {
+                    code: 0,
+                    msg: "".to_owned(),
+                })
+                .code
+                .eq(&200);
+        }
+        false
+    }
+
+    /// 云盘歌曲匹配曲库歌曲
+    /// uid: 用户 id
+    /// sid: 云盘歌曲 id
+    /// asid: 要匹配的曲库歌曲 id
+    #[allow(unused)]
+    pub async fn cloud_match(&self, uid: u64, sid: u64, asid: u64) -> bool {
+        let path = "/weapi/cloud/user/song/match";
+        let mut params = HashMap::new();
+        let uid = uid.to_string();
+        let sid = sid.to_string();
+        let asid = asid.to_string();
+        params.insert("userId", uid.as_str());
+        params.insert("songId", sid.as_str());
+        params.insert("adjustSongId", asid.as_str());
+        if let Ok(result) = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await
+        {
+            return to_msg(result)
+                .unwrap_or(Msg {
+                    code: 0,
+                    msg: "".to_owned(),
+                })
+                .code
+                .eq(&200);
+        }
+        false
+    }
+
+    /// 上传音乐到云盘
+    /// 返回云盘歌曲 id
+    /// file: 本地音乐文件路径，歌名、歌手、专辑从文件标签读取
+    /// progress: 上传进度回调
+    #[allow(unused)]
+    pub async fn cloud_upload<F>(&self, file: &Path, mut progress: F) -> Result<u64>
+    where
+        F: FnMut(UploadProgress),
+    {
+        progress(UploadProgress::Hashing);
+        let mut data = File::open(file)?;
+        let total = data.metadata()?.len();
+        let mut hasher = Hasher::new(MessageDigest::md5())?;
+        let mut buf = vec![0; NOS_CHUNK_SIZE];
+        loop {
+            let n = data.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n])?;
+        }
+        let md5 = hex::encode(hasher.finish()?);
+        let (tag, bitrate) = SongTag::read_from(file)?;
+        let filename = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let ext = file
+            .extension()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_else(|| "mp3".to_owned());
+        let song = if tag.title.is_empty() {
+            filename.clone()
+        } else {
+            tag.title.clone()
+        };
+        let artist = if tag.artists.is_empty() {
+            "未知艺术家".to_owned()
+        } else {
+            tag.artists.join("/")
+        };
+        let album = if tag.album.is_empty() {
+            "未知专辑".to_owned()
+        } else {
+            tag.album.clone()
+        };
+        let bitrate = bitrate.max(128000).to_string();
+        let size = total.to_string();
+
+        progress(UploadProgress::Checking);
+        let path = "/weapi/cloud/upload/check";
+        let mut params = HashMap::new();
+        params.insert("bitrate", &bitrate[..]);
+        params.insert("ext", "");
+        params.insert("length", &size[..]);
+        params.insert("md5", &md5[..]);
+        params.insert("songId", "0");
+        params.insert("version", "1");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let check = to_cloud_upload_check(result)?;
+
+        let path = "/weapi/nos/token/alloc";
+        let mut params = HashMap::new();
+        params.insert("bucket", "");
+        params.insert("ext", &ext[..]);
+        params.insert("filename", &filename[..]);
+        params.insert("local", "false");
+        params.insert("nos_product", "3");
+        params.insert("type", "audio");
+        params.insert("md5", &md5[..]);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let token = to_nos_token(result)?;
+
+        if check.need_upload {
+            data.seek(SeekFrom::Start(0))?;
+            self.nos_upload(&token, &mut data, total, mime_type(&ext), &mut progress)
+                .await?;
+        }
+
+        progress(UploadProgress::Submitting);
+        let path = "/weapi/upload/cloud/info/v2";
+        let mut params = HashMap::new();
+        params.insert("md5", &md5[..]);
+        params.insert("songid", &check.song_id[..]);
+        params.insert("filename", &filename[..]);
+        params.insert("song", &song[..]);
+        params.insert("album", &album[..]);
+        params.insert("artist", &artist[..]);
+        params.insert("bitrate", &bitrate[..]);
+        params.insert("resourceId", &token.resource_id[..]);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let song_id = to_cloud_song_id(result)?;
+
+        progress(UploadProgress::Publishing);
+        let path = "/weapi/cloud/pub/v2";
+        let mut params = HashMap::new();
+        params.insert("songid", &song_id[..]);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let msg = to_msg(result)?;
+        if msg.code != 200 && msg.code != 201 {
+            return Err(anyhow!("cloud publish err: {} {}", msg.code, msg.msg));
+        }
+        Ok(song_id.parse()?)
+    }
+
+    /// 分片上传文件到 NOS
+    async fn nos_upload<F>(
+        &self,
+        token: &NosToken,
+        data: &mut File,
+        total: u64,
+        mime: &str,
+        progress: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(UploadProgress),
+    {
+        progress(UploadProgress::Uploading(0, total));
+        let lbs: serde_json::Value =
+            serde_json::from_str(&self.client.get_async(NOS_LBS_URL).await?.text().await?)?;
+        let host = lbs
+            .get("upload")
+            .and_then(|u| u.get(0))
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| anyhow!("no nos upload host"))?;
+
+        let mut context = String::new();
+        let mut offset = 0;
+        while offset < total {
+            let end = (offset + NOS_CHUNK_SIZE as u64).min(total);
+            let complete = end == total;
+            // 逐块读取，避免将整个文件载入内存
+            let mut chunk = Vec::with_capacity((end - offset) as usize);
+            data.by_ref().take(end - offset).read_to_end(&mut chunk)?;
+            if chunk.len() as u64 != end - offset {
+                return Err(anyhow!("file changed during upload"));
+            }
+            let mut url = format!(
+                "{}/{}/{}?offset={}&complete={}&version=1.0",
+                host,
+                token.bucket,
+                encode_uri_component(&token.object_key),
+                offset,
+                complete
+            );
+            if !context.is_empty() {
+                url.push_str(&format!("&context={}", encode_uri_component(&context)));
+            }
+            let request = Request::post(url)
+                .header("x-nos-token", &token.token)
+                .header("Content-Type", mime)
+                .body(chunk)?;
+            let mut response = self.client.send_async(request).await?;
+            if !response.status().is_success() {
+                return Err(anyhow!("nos upload err: {}", response.status()));
+            }
+            let result: serde_json::Value = serde_json::from_str(&response.text().await?)?;
+            if let Some(ctx) = result.get("context").and_then(|c| c.as_str()) {
+                context = ctx.to_owned();
+            }
+            offset = end;
+            progress(UploadProgress::Uploading(offset, total));
+        }
+        Ok(())
     }
 
     /// 歌单详情
     /// songlist_id: 歌单 id
     #[allow(unused)]
     pub async fn song_list_detail(&self, songlist_id: u64) -> Result<PlayListDetail> {
+        to_mix_detail(&self.song_list_detail_value(songlist_id).await?)
+    }
+
+    async fn song_list_detail_value(&self, songlist_id: u64) -> Result<serde_json::Value> {
         let empty = String::new();
         let csrf_token = self.csrf.get().unwrap_or(&empty);
         let path = "/weapi/v6/playlist/detail";
@@ -490,7 +976,27 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        to_mix_detail(&serde_json::from_str(&result)?)
+        Ok(serde_json::from_str(&result)?)
+    }
+
+    /// 歌单收藏者
+    /// id: 歌单id
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn playlist_subscribers(&self, id: u64, offset: u16, limit: u16) -> Result<UserPage> {
+        let path = "/weapi/playlist/subscribers";
+        let mut params = HashMap::new();
+        let id = id.to_string();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("id", id.as_str());
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_user_list(result, Parse::Subscribers)
     }
 
     /// 歌曲详情
@@ -512,13 +1018,55 @@ impl MusicApi {
         to_song_info(result, Parse::Usl)
     }
 
-    /// 歌曲 URL
-    /// ids: 歌曲列表
-    /// br: 歌曲码率
-    ///     l: 128000
-    ///     m: 192000
-    ///     h: 320000
-    ///    sq: 999000
+    /// 歌曲权限
+    /// ids: 歌曲 id 列表
+    #[allow(unused)]
+    pub async fn songs_privilege(&self, ids: &[u64]) -> Result<Vec<Privilege>> {
+        let path = "/weapi/song/enhance/privilege";
+        let mut params = HashMap::new();
+        let ids = serde_json::to_string(ids)?;
+        params.insert("ids", &ids[..]);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_privileges(result)
+    }
+
+    /// 为缺少权限信息的歌曲批量获取权限
+    /// songs: 歌曲列表
+    #[allow(unused)]
+    pub async fn fill_privileges(&self, songs: &mut [SongInfo]) -> Result<()> {
+        let ids: Vec<u64> = songs
+            .iter()
+            .filter(|s| s.privilege.is_none())
+            .map(|s| s.id)
+            .collect();
+        for chunk in ids.chunks(500) {
+            for p in self.songs_privilege(chunk).await? {
+                for song in songs.iter_mut().filter(|s| s.id == p.id) {
+                    song.copyright = p.copyright();
+                    song.privilege = Some(p.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 列表未附带权限时补充获取，获取失败时保留原列表
+    async fn with_privileges(&self, mut songs: Vec<SongInfo>) -> Vec<SongInfo> {
+        if songs.iter().any(|s| s.privilege.is_none()) {
+            let _ = self.fill_privileges(&mut songs).await;
+        }
+        songs
+    }
+
+    /// 歌曲 URL
+    /// ids: 歌曲列表
+    /// br: 歌曲码率
+    ///     l: 128000
+    ///     m: 192000
+    ///     h: 320000
+    ///    sq: 999000
     ///    hr: 1900000
     #[allow(unused)]
     pub async fn songs_url(&self, ids: &[u64], br: &str) -> Result<Vec<SongUrl>> {
@@ -562,6 +1110,74 @@ impl MusicApi {
         to_song_url(result)
     }
 
+    /// 按音质阶梯获取最佳歌曲 URL
+    /// 从 preferred 开始逐级降低音质，直到 floor 为止
+    /// ids: 歌曲列表
+    /// preferred: 首选音质
+    /// floor: 最低可接受音质，须为立体声音质且不高于 preferred
+    #[allow(unused)]
+    pub async fn resolve_best_url(
+        &self,
+        ids: &[u64],
+        preferred: SongQuality,
+        floor: SongQuality,
+    ) -> Result<Vec<ResolvedSongUrl>> {
+        let mut resolved: Vec<ResolvedSongUrl> = ids
+            .iter()
+            .map(|&id| ResolvedSongUrl {
+                id,
+                url: None,
+                quality: SongQualityState {
+                    available: vec![],
+                    selected: Some(preferred),
+                    actual: None,
+                },
+                reason: None,
+            })
+            .collect();
+        for level in SongQuality::ladder(preferred, floor)? {
+            let pending: Vec<u64> = resolved
+                .iter()
+                .filter(|r| {
+                    r.quality.actual.is_none() && r.reason != Some(SongUnavailable::NoCopyright)
+                })
+                .map(|r| r.id)
+                .collect();
+            if pending.is_empty() {
+                break;
+            }
+            for url in self.songs_url_v1(&pending, level).await? {
+                let Some(r) = resolved.iter_mut().find(|r| r.id == url.id) else {
+                    continue;
+                };
+                if url.playable() {
+                    // 能获取到立体声音质时，更低的立体声音质也可获取
+                    // 环绕声/全景声不代表立体声音质可用，仅记录该音质
+                    r.quality.available = if url.quality.is_stereo() {
+                        SongQuality::ladder(url.quality, SongQuality::Standard).unwrap_or_default()
+                    } else {
+                        vec![url.quality]
+                    };
+                    r.quality.actual = Some(url.quality);
+                    r.url = Some(url);
+                } else {
+                    r.reason = r.reason.or(url.unavailable);
+                    if r.url.is_none() && !url.url.is_empty() {
+                        r.url = Some(url);
+                    }
+                }
+            }
+        }
+        // 仅可试听时以试听片段的音质为准
+        for r in resolved.iter_mut().filter(|r| r.quality.actual.is_none()) {
+            if let Some(url) = &r.url {
+                r.quality.available = vec![url.quality];
+                r.quality.actual = Some(url.quality);
+            }
+        }
+        Ok(resolved)
+    }
+
     /// 每日推荐歌单
     #[allow(unused)]
     pub async fn recommend_resource(&self) -> Result<Vec<SongList>> {
@@ -588,7 +1204,9 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        to_song_info(result, Parse::Rmds)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Rmds)?)
+            .await)
     }
 
     /// 私人FM
@@ -605,7 +1223,29 @@ impl MusicApi {
                 true,
             )
             .await?;
-        to_song_info(result, Parse::Rmd)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Rmd)?)
+            .await)
+    }
+
+    /// 私人FM (指定模式)
+    /// mode: FM 模式
+    #[allow(unused)]
+    pub async fn personal_fm_mode(&self, mode: FmMode) -> Result<Vec<SongInfo>> {
+        let path = "/weapi/v1/radio/get";
+        let mut params = HashMap::new();
+        let (mode, sub_mode) = mode.as_params();
+        params.insert("mode", mode);
+        if let Some(sub_mode) = sub_mode {
+            params.insert("subMode", sub_mode);
+        }
+        params.insert("limit", "3");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Rmd)?)
+            .await)
     }
 
     /// 收藏/取消收藏
@@ -613,14 +1253,24 @@ impl MusicApi {
     /// like: true 收藏，false 取消
     #[allow(unused)]
     pub async fn like(&self, like: bool, songid: u64) -> bool {
+        self.fm_like(like, songid, 25).await
+    }
+
+    /// FM 收藏/取消收藏
+    /// like: true 收藏，false 取消
+    /// songid: 歌曲id
+    /// played_seconds: 已播放时长(秒)
+    #[allow(unused)]
+    pub async fn fm_like(&self, like: bool, songid: u64, played_seconds: u64) -> bool {
         let path = "/weapi/radio/like";
         let mut params = HashMap::new();
         let songid = songid.to_string();
         let like = like.to_string();
+        let time = played_seconds.to_string();
         params.insert("alg", "itembased");
         params.insert("trackId", songid.as_str());
         params.insert("like", like.as_str());
-        params.insert("time", "25");
+        params.insert("time", time.as_str());
         if let Ok(result) = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await
@@ -640,12 +1290,21 @@ impl MusicApi {
     /// songid: 歌曲id
     #[allow(unused)]
     pub async fn fm_trash(&self, songid: u64) -> bool {
+        self.fm_dislike(songid, 25).await
+    }
+
+    /// FM 不喜欢
+    /// songid: 歌曲id
+    /// played_seconds: 已播放时长(秒)
+    #[allow(unused)]
+    pub async fn fm_dislike(&self, songid: u64, played_seconds: u64) -> bool {
         let path = "/weapi/radio/trash/add";
         let mut params = HashMap::new();
         let songid = songid.to_string();
+        let time = played_seconds.to_string();
         params.insert("alg", "RT");
         params.insert("songId", songid.as_str());
-        params.insert("time", "25");
+        params.insert("time", time.as_str());
         if let Ok(result) = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await
@@ -661,6 +1320,40 @@ impl MusicApi {
         false
     }
 
+    /// 上报播放记录
+    /// song_id: 歌曲 id
+    /// source_id: 来源 id (歌单、专辑等)
+    /// played_seconds: 已播放时长(秒)
+    #[allow(unused)]
+    pub async fn scrobble(&self, song_id: u64, source_id: u64, played_seconds: u64) -> bool {
+        let event = PlayEvent::End {
+            song_id,
+            source_id,
+            played_seconds,
+            end: PlayEnd::PlayEnd,
+        };
+        self.report_play_events(&[event]).await.is_ok()
+    }
+
+    /// 批量上报播放事件
+    /// events: 播放事件
+    #[allow(unused)]
+    pub async fn report_play_events(&self, events: &[PlayEvent]) -> Result<()> {
+        let path = "/weapi/feedback/weblog";
+        let mut params = HashMap::new();
+        let logs = serde_json::Value::Array(events.iter().map(PlayEvent::to_log).collect());
+        let logs = logs.to_string();
+        params.insert("logs", logs.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let msg = to_msg(result)?;
+        if msg.code != 200 {
+            return Err(anyhow!("weblog err: {} {}", msg.code, msg.msg));
+        }
+        Ok(())
+    }
+
     /// 搜索
     /// keywords: 关键词
     /// types: 1: 单曲, 10: 专辑, 100: 歌手, 1000: 歌单, 1002: 用户, 1004: MV, 1006: 歌词, 1009: 电台, 1014: 视频
@@ -687,6 +1380,84 @@ impl MusicApi {
             .await
     }
 
+    /// 按类型搜索
+    /// keywords: 关键词
+    /// kind: 搜索类型
+    /// offset: 起始点
+    /// limit: 数量
+    #[allow(unused)]
+    pub async fn search_typed(
+        &self,
+        keywords: String,
+        kind: SearchType,
+        offset: u16,
+        limit: u16,
+    ) -> Result<SearchResult> {
+        let result = self.search(keywords, kind.as_code(), offset, limit).await?;
+        to_search_result(result, kind, offset)
+    }
+
+    /// 搜索建议
+    /// keywords: 关键词
+    #[allow(unused)]
+    pub async fn search_suggest(&self, keywords: String) -> Result<Vec<SearchSuggest>> {
+        let path = "/weapi/search/suggest/web";
+        let mut params = HashMap::new();
+        params.insert("s", &keywords[..]);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_search_suggest(result)
+    }
+
+    /// 热搜列表
+    #[allow(unused)]
+    pub async fn search_hot_detail(&self) -> Result<Vec<HotSearch>> {
+        let path = "/weapi/hotsearchlist/get";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_hot_search(result)
+    }
+
+    /// 默认搜索关键词
+    #[allow(unused)]
+    pub async fn search_default_keyword(&self) -> Result<DefaultKeyword> {
+        let path = "/weapi/search/defaultkeyword/get";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_default_keyword(result)
+    }
+
+    /// 搜索多重匹配
+    /// keywords: 关键词
+    #[allow(unused)]
+    pub async fn search_multimatch(&self, keywords: String) -> Result<MultiMatch> {
+        let path = "/weapi/search/suggest/multimatch";
+        let mut params = HashMap::new();
+        params.insert("s", &keywords[..]);
+        params.insert("type", "1");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_multi_match(result)
+    }
+
     /// 搜索单曲
     /// keywords: 关键词
     /// offset: 起始点
@@ -699,7 +1470,9 @@ impl MusicApi {
         limit: u16,
     ) -> Result<Vec<SongInfo>> {
         let result = self.search(keywords, 1, offset, limit).await?;
-        to_song_info(result, Parse::Search)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Search)?)
+            .await)
     }
 
     /// 搜索歌手
@@ -762,6 +1535,36 @@ impl MusicApi {
         to_song_info(result, Parse::Search)
     }
 
+    /// 搜索歌词 (含匹配的歌词片段)
+    /// keywords: 关键词
+    /// offset: 起始点
+    /// limit: 数量
+    #[allow(unused)]
+    pub async fn search_lyric_matches(
+        &self,
+        keywords: String,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<LyricMatch>> {
+        let result = self.search(keywords, 1006, offset, limit).await?;
+        to_lyric_matches(result)
+    }
+
+    /// 搜索用户
+    /// keywords: 关键词
+    /// offset: 起始点
+    /// limit: 数量
+    #[allow(unused)]
+    pub async fn search_user(
+        &self,
+        keywords: String,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<UserProfile>> {
+        let result = self.search(keywords, 1002, offset, limit).await?;
+        Ok(to_user_list(result, Parse::Search)?.users)
+    }
+
     /// 获取歌手热门单曲
     /// id: 歌手 ID
     #[allow(unused)]
@@ -771,7 +1574,9 @@ impl MusicApi {
         let result = self
             .request(Method::Post, &path, params, CryptoApi::Weapi, "", false)
             .await?;
-        to_song_info(result, Parse::Singer)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Singer)?)
+            .await)
     }
 
     /// 获取歌手全部单曲
@@ -803,7 +1608,9 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", false)
             .await?;
-        to_song_info(result, Parse::SingerSongs)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::SingerSongs)?)
+            .await)
     }
 
     /// 全部新碟
@@ -826,6 +1633,66 @@ impl MusicApi {
         to_song_list(result, Parse::Album)
     }
 
+    /// 最新专辑
+    #[allow(unused)]
+    pub async fn album_new_newest(&self) -> Result<Vec<SongList>> {
+        let path = "/weapi/discovery/newAlbum";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_song_list(result, Parse::Album)
+    }
+
+    /// 数字专辑语种风格馆
+    /// area: Z_H:华语,E_A:欧美,KR:韩国,JP:日本
+    /// offset: 起始点
+    /// limit: 数量
+    #[allow(unused)]
+    pub async fn album_list_by_style(
+        &self,
+        area: &str,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SongList>> {
+        let path = "/weapi/vipmall/appalbum/album/style";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("area", area);
+        params.insert("offset", &offset[..]);
+        params.insert("limit", &limit[..]);
+        params.insert("total", "true");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_song_list(result, Parse::AlbumProduct)
+    }
+
+    /// 已购买的数字专辑
+    /// offset: 起始点
+    /// limit: 数量
+    #[allow(unused)]
+    pub async fn album_purchased(&self, offset: u16, limit: u16) -> Result<Vec<SongList>> {
+        let path = "/weapi/digitalAlbum/purchased";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", &offset[..]);
+        params.insert("limit", &limit[..]);
+        params.insert("total", "true");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_song_list(result, Parse::PaidAlbum)
+    }
+
     /// 专辑
     /// album_id: 专辑 id
     #[allow(unused)]
@@ -875,15 +1742,13 @@ impl MusicApi {
     /// 热门推荐歌单
     /// offset: 起始点
     /// limit: 数量
-    /// order: 排序方式:
-    //	      "hot": 热门，
-    ///        "new": 最新
-    /// cat: 全部,华语,欧美,日语,韩语,粤语,小语种,流行,摇滚,民谣,电子,舞曲,说唱,轻音乐,爵士,乡村,R&B/Soul,古典,民族,英伦,金属,朋克,蓝调,雷鬼,世界音乐,拉丁,另类/独立,New Age,古风,后摇,Bossa Nova,清晨,夜晚,学习,工作,午休,下午茶,地铁,驾车,运动,旅行,散步,酒吧,怀旧,清新,浪漫,性感,伤感,治愈,放松,孤独,感动,兴奋,快乐,安静,思念,影视原声,ACG,儿童,校园,游戏,70后,80后,90后,网络歌曲,KTV,经典,翻唱,吉他,钢琴,器乐,榜单,00后
+    /// order: 排序方式
+    /// cat: 分类名，见 playlist_catalogue，如 全部,华语,欧美,日语,韩语,粤语,小语种,流行,摇滚,民谣,电子,舞曲,说唱,轻音乐,爵士,乡村,R&B/Soul,古典,民族,英伦,金属,朋克,蓝调,雷鬼,世界音乐,拉丁,另类/独立,New Age,古风,后摇,Bossa Nova,清晨,夜晚,学习,工作,午休,下午茶,地铁,驾车,运动,旅行,散步,酒吧,怀旧,清新,浪漫,性感,伤感,治愈,放松,孤独,感动,兴奋,快乐,安静,思念,影视原声,ACG,儿童,校园,游戏,70后,80后,90后,网络歌曲,KTV,经典,翻唱,吉他,钢琴,器乐,榜单,00后
     #[allow(unused)]
     pub async fn top_song_list(
         &self,
         cat: &str,
-        order: &str,
+        order: PlaylistOrder,
         offset: u16,
         limit: u16,
     ) -> Result<Vec<SongList>> {
@@ -892,7 +1757,7 @@ impl MusicApi {
         let offset = offset.to_string();
         let limit = limit.to_string();
         params.insert("cat", cat);
-        params.insert("order", order);
+        params.insert("order", order.as_str());
         params.insert("total", "true");
         params.insert("offset", &offset[..]);
         params.insert("limit", &limit[..]);
@@ -902,6 +1767,40 @@ impl MusicApi {
         to_song_list(result, Parse::Top)
     }
 
+    /// 歌单分类
+    #[allow(unused)]
+    pub async fn playlist_catalogue(&self) -> Result<PlaylistCatalogue> {
+        let path = "/weapi/playlist/catalogue";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_playlist_catalogue(result)
+    }
+
+    /// 热门歌单标签
+    #[allow(unused)]
+    pub async fn playlist_hot_tags(&self) -> Result<Vec<PlaylistTag>> {
+        let path = "/weapi/playlist/hottags";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_playlist_hot_tags(result)
+    }
+
     /// 精品歌单
     /// lasttime: 分页参数,取上一页最后一个歌单的 updateTime 获取下一页数据
     /// limit: 数量
@@ -964,6 +1863,30 @@ impl MusicApi {
         self.song_list_detail(list_id).await
     }
 
+    /// 排行榜详情 (含分类及前三名)
+    #[allow(unused)]
+    pub async fn toplist_detail(&self) -> Result<Vec<ToplistDetail>> {
+        let path = "/weapi/toplist/detail";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_toplist_detail(result)
+    }
+
+    /// 排行榜歌曲 (含排名变化)
+    /// list_id: 排行榜 id
+    #[allow(unused)]
+    pub async fn top_songs_ranked(&self, list_id: u64) -> Result<Vec<RankedSong>> {
+        to_ranked_songs(&self.song_list_detail_value(list_id).await?)
+    }
+
     /// 查询歌词
     /// music_id: 歌曲id
     #[allow(unused)]
@@ -1040,25 +1963,185 @@ impl MusicApi {
         false
     }
 
+    /// MV 详情
+    /// id: MV id
+    #[allow(unused)]
+    pub async fn mv_detail(&self, id: u64) -> Result<MvDetail> {
+        let path = "/weapi/v1/mv/detail";
+        let mut params = HashMap::new();
+        let id = id.to_string();
+        params.insert("id", id.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_mv_detail(result)
+    }
+
+    /// MV 播放地址
+    /// id: MV id
+    /// resolution: 分辨率 (240/480/720/1080)
+    #[allow(unused)]
+    pub async fn mv_url(&self, id: u64, resolution: u32) -> Result<VideoUrl> {
+        let path = "/weapi/song/enhance/play/mv/url";
+        let mut params = HashMap::new();
+        let id = id.to_string();
+        let resolution = resolution.to_string();
+        params.insert("id", id.as_str());
+        params.insert("r", resolution.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_mv_url(result)
+    }
+
+    /// 收藏/取消收藏 MV
+    /// like: true 收藏，false 取消
+    /// id: MV id
+    #[allow(unused)]
+    pub async fn mv_subscribe(&self, like: bool, id: u64) -> bool {
+        let path = if like {
+            "/weapi/mv/sub"
+        } else {
+            "/weapi/mv/unsub"
+        };
+        let mut params = HashMap::new();
+        let ids = format!(r#"["{}"]"#, id);
+        let id = id.to_string();
+        params.insert("mvId", id.as_str());
+        params.insert("mvIds", ids.as_str());
+        if let Ok(result) = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await
+        {
+            return to_msg(result)
+                .unwrap_or(Msg {
+                    code: 0,
+                    msg: "".to_owned(),
+                })
+                .code
+                .eq(&200);
+        }
+        false
+    }
+
+    /// 歌手 MV 列表
+    /// id: 歌手 id
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn artist_mvs(&self, id: u64, offset: u16, limit: u16) -> Result<Vec<MvInfo>> {
+        let path = "/weapi/artist/mvs";
+        let mut params = HashMap::new();
+        let id = id.to_string();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("artistId", id.as_str());
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        params.insert("total", "true");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_mv_list(result)
+    }
+
+    /// 相似 MV
+    /// id: MV id
+    #[allow(unused)]
+    pub async fn mv_related(&self, id: u64) -> Result<Vec<MvInfo>> {
+        let path = "/weapi/discovery/simiMV";
+        let mut params = HashMap::new();
+        let id = id.to_string();
+        params.insert("mvid", id.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_mv_list(result)
+    }
+
+    /// 视频详情
+    /// id: 视频 id
+    #[allow(unused)]
+    pub async fn video_detail(&self, id: &str) -> Result<VideoDetail> {
+        let path = "/weapi/cloudvideo/v1/video/detail";
+        let mut params = HashMap::new();
+        params.insert("id", id);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_video_detail(result)
+    }
+
+    /// 视频播放地址
+    /// id: 视频 id
+    /// resolution: 分辨率 (240/480/720/1080)
+    #[allow(unused)]
+    pub async fn video_url(&self, id: &str, resolution: u32) -> Result<VideoUrl> {
+        let path = "/weapi/cloudvideo/playurl";
+        let mut params = HashMap::new();
+        let ids = format!(r#"["{}"]"#, id);
+        let resolution = resolution.to_string();
+        params.insert("ids", ids.as_str());
+        params.insert("resolution", resolution.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_video_url(result)
+    }
+
     /// 获取 APP 首页信息
+    /// client_type: 客户端类型
+    /// refresh: 是否刷新推荐内容
+    /// cursor: 翻页游标，首页为 None
     #[allow(unused)]
-    pub async fn homepage(&self, client_type: ClientType) -> Result<String> {
+    pub async fn homepage(
+        &self,
+        client_type: ClientType,
+        refresh: bool,
+        cursor: Option<&str>,
+    ) -> Result<HomepagePage> {
         let path = "/api/homepage/block/page";
         let mut params = HashMap::new();
-        params.insert("refresh", "false");
-        params.insert("cursor", "null");
-        self.request(Method::Post, path, params, CryptoApi::Weapi, "", true)
-            .await
+        let client = client_type.to_string();
+        params.insert("clientType", client.as_str());
+        params.insert("refresh", if refresh { "true" } else { "false" });
+        params.insert("cursor", cursor.unwrap_or("null"));
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                client_type.user_agent(),
+                true,
+            )
+            .await?;
+        to_homepage(result)
     }
 
     /// 获取首页轮播图
     #[allow(unused)]
     pub async fn banners(&self) -> Result<Vec<BannersInfo>> {
+        self.banners_for(ClientType::Pc).await
+    }
+
+    /// 获取指定客户端的首页轮播图
+    /// client_type: 客户端类型
+    #[allow(unused)]
+    pub async fn banners_for(&self, client_type: ClientType) -> Result<Vec<BannersInfo>> {
         let path = "/weapi/v2/banner/get";
         let mut params = HashMap::new();
-        params.insert("clientType", "pc");
+        let client = client_type.to_string();
+        params.insert("clientType", client.as_str());
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                client_type.user_agent(),
+                true,
+            )
             .await?;
         to_banners_info(result)
     }
@@ -1074,19 +2157,33 @@ impl MusicApi {
         I: Into<String>,
     {
         if !path.exists() {
-            let url = url.into();
-            let image_url = format!("{}?param={}y{}", url, width, high);
-
-            let mut response = self.client.get_async(image_url).await?;
-            if response.status().is_success() {
-                let mut buf = vec![];
-                response.copy_to(&mut buf).await?;
+            if let Some(buf) = self.fetch_img(url, width, high).await? {
                 std::fs::write(&path, buf)?;
             }
         }
         Ok(())
     }
 
+    /// 从网络获取图片数据
+    /// url: 网址
+    /// width: 宽度
+    /// high: 高度
+    async fn fetch_img<I>(&self, url: I, width: u16, high: u16) -> Result<Option<Vec<u8>>>
+    where
+        I: Into<String>,
+    {
+        let url = url.into();
+        let image_url = format!("{}?param={}y{}", url, width, high);
+
+        let mut response = self.client.get_async(image_url).await?;
+        if response.status().is_success() {
+            let mut buf = vec![];
+            response.copy_to(&mut buf).await?;
+            return Ok(Some(buf));
+        }
+        Ok(None)
+    }
+
     /// 从网络下载音乐
     /// url: 网址
     /// path: 本地保存路径(包含文件名)
@@ -1106,6 +2203,86 @@ impl MusicApi {
         Ok(())
     }
 
+    /// 按文件名模板下载音乐
+    /// 返回实际保存路径
+    /// url: 歌曲 URL
+    /// song: 歌曲信息
+    /// album: 专辑详情
+    /// dir: 保存目录
+    /// template: 文件名模板
+    #[allow(unused)]
+    pub async fn download_song_to(
+        &self,
+        url: &SongUrl,
+        song: &SongInfo,
+        album: Option<&AlbumDetail>,
+        dir: &Path,
+        template: &FileNameTemplate,
+    ) -> Result<PathBuf> {
+        if url.url.is_empty() {
+            return Err(anyhow!(
+                "song {} unavailable: {:?}",
+                url.id,
+                url.unavailable
+            ));
+        }
+        let path = unique_path(&dir.join(template.render(song, album, url)?));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        self.download_song(url.url.as_str(), path.clone()).await?;
+        Ok(path)
+    }
+
+    /// 获取歌曲标签 (包含专辑信息、封面和歌词)
+    /// song: 歌曲信息
+    /// album: 专辑详情，为 None 时按 song.album_id 获取
+    #[allow(unused)]
+    pub async fn song_tag(&self, song: &SongInfo, album: Option<&AlbumDetail>) -> Result<SongTag> {
+        let mut tag = SongTag::from_song(song);
+        // 专辑获取失败时仍返回不含专辑信息的标签
+        let fetched = match album {
+            None if song.album_id != 0 => self.album(song.album_id).await.ok(),
+            _ => None,
+        };
+        if let Some(album) = album.or(fetched.as_ref()) {
+            tag = tag.with_album(album, song.id);
+        }
+        // 封面获取失败时仍返回不含封面的标签
+        if !song.pic_url.is_empty() {
+            tag.cover = self
+                .fetch_img(song.pic_url.as_str(), 800, 800)
+                .await
+                .ok()
+                .flatten();
+        }
+        if let Ok(lyrics) = self.song_lyric(song.id).await {
+            tag = tag.with_lyrics(&lyrics);
+        }
+        Ok(tag)
+    }
+
+    /// 从网络下载音乐并写入标签
+    /// url: 网址
+    /// path: 本地保存路径(包含文件名)
+    /// tag: 歌曲标签，可由 song_tag 获取
+    #[allow(unused)]
+    pub async fn download_song_with_tag<I>(
+        &self,
+        url: I,
+        path: PathBuf,
+        tag: &SongTag,
+    ) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.download_song(url, path.clone()).await?;
+        if path.exists() {
+            tag.write_to(&path)?;
+        }
+        Ok(())
+    }
+
     /// 用户电台定阅列表
     /// offset: 列表起点号
     /// limit: 列表长度
@@ -1142,7 +2319,152 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        to_song_info(result, Parse::Radio)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Radio)?)
+            .await)
+    }
+
+    /// 电台节目列表
+    /// rid: 电台ID
+    /// asc: true 按期数升序，false 降序
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn radio_programs(
+        &self,
+        rid: u64,
+        asc: bool,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<RadioProgram>> {
+        let path = "/weapi/dj/program/byradio";
+        let mut params = HashMap::new();
+        let id = rid.to_string();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("radioId", id.as_str());
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        params.insert("asc", if asc { "true" } else { "false" });
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_radio_programs(result)
+    }
+
+    /// 电台详情
+    /// rid: 电台ID
+    #[allow(unused)]
+    pub async fn radio_detail(&self, rid: u64) -> Result<Radio> {
+        let path = "/weapi/djradio/v2/get";
+        let mut params = HashMap::new();
+        let id = rid.to_string();
+        params.insert("id", id.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_radio_detail(result)
+    }
+
+    /// 电台分类
+    #[allow(unused)]
+    pub async fn radio_categories(&self) -> Result<Vec<RadioCategory>> {
+        let path = "/weapi/djradio/category/get";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_radio_categories(result)
+    }
+
+    /// 分类推荐电台
+    /// cate_id: 分类 id
+    #[allow(unused)]
+    pub async fn radio_recommend_by_category(&self, cate_id: u64) -> Result<Vec<Radio>> {
+        let path = "/weapi/djradio/recommend";
+        let mut params = HashMap::new();
+        let cate_id = cate_id.to_string();
+        params.insert("cateId", cate_id.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_radio_list(result)
+    }
+
+    /// 热门电台
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    #[allow(unused)]
+    pub async fn radio_hot(&self, offset: u16, limit: u16) -> Result<Vec<Radio>> {
+        let path = "/weapi/djradio/hot/v1";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_radio_list(result)
+    }
+
+    /// 订阅/取消订阅电台
+    /// like: true 订阅，false 取消
+    /// rid: 电台ID
+    #[allow(unused)]
+    pub async fn radio_subscribe(&self, like: bool, rid: u64) -> bool {
+        let path = if like {
+            "/weapi/djradio/sub"
+        } else {
+            "/weapi/djradio/unsub"
+        };
+        let mut params = HashMap::new();
+        let id = rid.to_string();
+        params.insert("id", id.as_str());
+        if let Ok(result) = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await
+        {
+            return to_msg(result)
+                .unwrap_or(Msg {
+                    code: 0,
+                    msg: "".to_owned(),
+                })
+                .code
+                .eq(&200);
+        }
+        false
+    }
+
+    /// 生成电台的 RSS 文档
+    /// rid: 电台ID
+    /// limit: 最新的节目数
+    /// enclosure_base: 节目音频的地址前缀，为空时直接使用歌曲 URL (有效期较短)
+    #[allow(unused)]
+    pub async fn radio_feed(
+        &self,
+        rid: u64,
+        limit: u16,
+        enclosure_base: Option<&str>,
+    ) -> Result<String> {
+        let radio = self.radio_detail(rid).await?;
+        let programs = self.radio_programs(rid, false, 0, limit).await?;
+        let ids: Vec<u64> = programs.iter().map(|p| p.song.id).collect();
+        let urls = self.songs_url_v1(&ids, SongQuality::Extreme).await?;
+        Ok(radio_rss(&radio, &programs, |p| {
+            let url = urls
+                .iter()
+                .find(|u| u.id == p.song.id && !u.url.is_empty())?;
+            let local = enclosure_base
+                .map(|base| format!("{}/{}.{}", base, p.song.id, template::extension(url)));
+            Some(Enclosure::from_song_url(url, local))
+        }))
     }
 
     /// 心动模式/智能播放
@@ -1162,7 +2484,39 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        to_song_info(result, Parse::Intelligence)
+        Ok(self
+            .with_privileges(to_song_info(result, Parse::Intelligence)?)
+            .await)
+    }
+
+    /// 心动模式/智能播放 (含推荐标记)
+    /// sid: 歌曲ID，以此歌曲为参考推荐
+    /// pid: 歌单ID
+    /// start_id: 起始歌曲ID
+    /// count: 数量
+    #[allow(unused)]
+    pub async fn playmode_intelligence(
+        &self,
+        sid: u64,
+        pid: u64,
+        start_id: u64,
+        count: u16,
+    ) -> Result<Vec<IntelligenceTrack>> {
+        let path = "/weapi/playmode/intelligence/list";
+        let mut params = HashMap::new();
+        let id = sid.to_string();
+        let pid = pid.to_string();
+        let start_id = start_id.to_string();
+        let count = count.to_string();
+        params.insert("songId", id.as_str());
+        params.insert("type", "fromPlayOne");
+        params.insert("playlistId", pid.as_str());
+        params.insert("startMusicId", start_id.as_str());
+        params.insert("count", count.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_intelligence_tracks(result)
     }
 }
 
@@ -1196,6 +2550,35 @@ fn build_cookie(cryptoapi: &CryptoApi) -> String {
     )
 }
 
+fn encode_uri_component(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 根据扩展名确定 MIME 类型
+pub(crate) fn mime_type(ext: &str) -> &'static str {
+    match ext.to_ascii_lowercase().as_str() {
+        "flac" => "audio/flac",
+        "m4a" | "aac" => "audio/mp4",
+        _ => "audio/mpeg",
+    }
+}
+
 fn choose_user_agent(ua: &str) -> &str {
     let index = if ua == "mobile" {
         rand::random::<u16>() % 7
@@ -4,9 +4,13 @@
 // Distributed under terms of the GPLv3 license.
 //
 use anyhow::{anyhow, Context, Ok, Result};
+use openssl::hash::{hash, MessageDigest};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::fmt;
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 trait DeVal<'a>: Sized {
     fn dval(v: &'a Value) -> Result<Self>;
@@ -42,6 +46,12 @@ impl<'a> DeVal<'a> for u32 {
     }
 }
 
+impl<'a> DeVal<'a> for f64 {
+    fn dval(v: &Value) -> Result<Self> {
+        Ok(Self::deserialize(v)?)
+    }
+}
+
 impl<'a> DeVal<'a> for String {
     fn dval(v: &Value) -> Result<Self> {
         Ok(Self::deserialize(v)?)
@@ -155,7 +165,7 @@ pub fn to_singer_info(json: String) -> Result<Vec<SingerInfo>> {
 }
 
 /// 音质等级
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Default)]
 pub enum SongQuality {
     #[default]
     Standard,
@@ -208,19 +218,117 @@ impl SongQuality {
             _ => Self::Master,
         }
     }
+
+    /// 立体声音质，由低到高
+    const STEREO: [Self; 6] = [
+        Self::Standard,
+        Self::Higher,
+        Self::Extreme,
+        Self::Lossless,
+        Self::HiRes,
+        Self::Master,
+    ];
+
+    /// 是否为立体声音质
+    pub fn is_stereo(&self) -> bool {
+        Self::STEREO.contains(self)
+    }
+
+    /// 音质阶梯，从 top 到 bottom 由高到低排列
+    /// top 为环绕声/全景声时，其后回退到立体声 Hi-Res
+    /// bottom 必须为立体声音质且不高于 top
+    pub fn ladder(top: Self, bottom: Self) -> Result<Vec<Self>> {
+        let pos = |q: Self| Self::STEREO.iter().position(|s| *s == q);
+        let b = pos(bottom).ok_or_else(|| anyhow!("{:?} is not a stereo quality", bottom))?;
+        let (head, t) = match pos(top) {
+            Some(t) => (None, t),
+            None => (Some(top), pos(Self::HiRes).unwrap_or_default()),
+        };
+        if b > t {
+            return Err(anyhow!("quality floor {:?} is above {:?}", bottom, top));
+        }
+        Ok(head
+            .into_iter()
+            .chain(Self::STEREO[b..=t].iter().rev().copied())
+            .collect())
+    }
 }
 
 /// 歌曲 URL
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct SongUrl {
     /// 歌曲 id
     pub id: u64,
-    /// 歌曲 URL
+    /// 歌曲 URL，不可用时为空
     pub url: String,
     /// 码率
     pub rate: u32,
     /// 音质等级
     pub quality: SongQuality,
+    /// 文件大小
+    #[serde(default)]
+    pub size: u64,
+    /// 文件 md5
+    #[serde(default)]
+    pub md5: String,
+    /// 文件类型 (mp3/flac/m4a)
+    #[serde(default)]
+    pub file_type: String,
+    /// 编码类型
+    #[serde(default)]
+    pub encode_type: String,
+    /// URL 有效期(秒)
+    #[serde(default)]
+    pub expi: u64,
+    /// 收费类型
+    /// 0: 免费
+    /// 1: VIP
+    /// 4: 购买专辑
+    /// 8: 低音质免费
+    #[serde(default)]
+    pub fee: i32,
+    /// 试听信息，非空时 URL 为试听片段
+    #[serde(default)]
+    pub free_trial: Option<FreeTrialInfo>,
+    /// 状态码
+    #[serde(default)]
+    pub code: i32,
+    /// 不可用原因，可正常播放时为 None
+    #[serde(default)]
+    pub unavailable: Option<SongUnavailable>,
+}
+
+impl SongUrl {
+    /// 是否为试听片段
+    pub fn is_trial(&self) -> bool {
+        self.free_trial.is_some()
+    }
+
+    /// 是否可完整播放
+    pub fn playable(&self) -> bool {
+        !self.url.is_empty() && self.unavailable.is_none()
+    }
+
+    /// URL 有效期
+    pub fn expires_in(&self) -> Duration {
+        Duration::from_secs(self.expi)
+    }
+
+    /// 校验下载数据的 md5
+    pub fn check_md5(&self, data: &[u8]) -> bool {
+        hash(MessageDigest::md5(), data)
+            .map(|digest| hex::encode(digest).eq_ignore_ascii_case(&self.md5))
+            .unwrap_or(false)
+    }
+}
+
+/// 试听信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FreeTrialInfo {
+    /// 试听开始时间(秒)
+    pub start: u64,
+    /// 试听结束时间(秒)
+    pub end: u64,
 }
 
 #[allow(unused)]
@@ -231,32 +339,46 @@ pub fn to_song_url(json: String) -> Result<Vec<SongUrl>> {
         let mut vec: Vec<SongUrl> = Vec::new();
         let array: &Vec<Value> = get_val!(value, "data")?;
         for v in array.iter() {
-            let url: String = get_val!(v, "url").unwrap_or_default();
-            if !url.is_empty() {
-                let rate: u32 = get_val!(v, "br")?;
-                vec.push(SongUrl {
-                    id: get_val!(v, "id")?,
-                    url,
-                    rate,
-                    quality: get_val!(v, "level")
-                        .ok()
-                        .and_then(|level: String| SongQuality::from_level(&level))
-                        .unwrap_or_else(|| SongQuality::from_rate(rate)),
-                });
-            }
+            let rate: u32 = get_val!(v, "br").unwrap_or_default();
+            let free_trial = match v.get("freeTrialInfo") {
+                Some(t) if !t.is_null() => Some(FreeTrialInfo {
+                    start: get_val!(t, "start").unwrap_or_default(),
+                    end: get_val!(t, "end").unwrap_or_default(),
+                }),
+                _ => None,
+            };
+            vec.push(SongUrl {
+                id: get_val!(v, "id")?,
+                url: get_val!(v, "url").unwrap_or_default(),
+                rate,
+                quality: get_val!(v, "level")
+                    .ok()
+                    .and_then(|level: String| SongQuality::from_level(&level))
+                    .unwrap_or_else(|| SongQuality::from_rate(rate)),
+                size: get_val!(v, "size").unwrap_or_default(),
+                md5: get_val!(v, "md5").unwrap_or_default(),
+                file_type: get_val!(v, "type").unwrap_or_default(),
+                encode_type: get_val!(v, "encodeType").unwrap_or_default(),
+                expi: get_val!(v, "expi").unwrap_or_default(),
+                fee: get_val!(v, "fee").unwrap_or_default(),
+                free_trial,
+                code: get_val!(v, "code").unwrap_or_default(),
+                unavailable: SongUnavailable::from_url_entry(v),
+            });
         }
         return Ok(vec);
     }
     Err(anyhow!("none"))
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize, Clone)]
 pub enum SongCopyright {
     Free,
     VipOnly,
     Payment,
     VipOnlyHighRate,
     Unavailable,
+    #[default]
     Unknown,
 }
 
@@ -275,16 +397,19 @@ impl SongCopyright {
         // let subp: i32 = get_val!(v, "subp")?;
         // let cp: i32 = get_val!(v, "cp")?;
 
-        // seems 'st' is enough for unavailable
         let st: i32 = get_val!(v, "st")?;
         let fee: i32 = get_val!(v, "fee")?;
+        let pl: u32 = get_val!(v, "pl").unwrap_or_default();
+        Ok(Self::from_status(st, fee, pl))
+    }
 
-        let res = if st < 0 {
+    /// st < 0 或免费歌曲无播放码率时不可用
+    fn from_status(st: i32, fee: i32, pl: u32) -> Self {
+        if st < 0 || (fee == 0 && pl == 0) {
             Self::Unavailable
         } else {
             Self::from_fee(fee)
-        };
-        Ok(res)
+        }
     }
 
     pub fn playable(&self) -> bool {
@@ -292,6 +417,160 @@ impl SongCopyright {
     }
 }
 
+/// 歌曲权限
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Privilege {
+    /// 歌曲 id
+    pub id: u64,
+    /// 收费类型
+    /// 0: 免费
+    /// 1: VIP
+    /// 4: 购买专辑
+    /// 8: 低音质免费
+    pub fee: i32,
+    /// 状态，小于 0 为已下架
+    pub st: i32,
+    /// 可播放码率，0 为不可播放
+    pub pl: u32,
+    /// 可下载码率，0 为不可下载
+    pub dl: u32,
+    /// 最高码率
+    pub maxbr: u32,
+    /// 最高音质
+    pub max_level: Option<SongQuality>,
+    /// 可播放最高音质
+    pub play_level: Option<SongQuality>,
+    /// 可下载最高音质
+    pub download_level: Option<SongQuality>,
+    /// 是否可分享
+    pub sp: i32,
+    /// 是否有版权
+    pub cp: i32,
+    /// 是否可收藏
+    pub subp: i32,
+    /// 各码率付费信息
+    pub charge_info: Vec<ChargeInfo>,
+}
+
+/// 付费信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChargeInfo {
+    /// 码率
+    pub rate: u32,
+    /// 付费类型
+    /// 0: 免费
+    /// 1: VIP
+    pub charge_type: i32,
+}
+
+impl Privilege {
+    pub fn from_value(v: &Value) -> Result<Self> {
+        let level = |name: &str| {
+            get_val!(@as String, v, name)
+                .ok()
+                .and_then(|l| SongQuality::from_level(&l))
+        };
+        let charge_info = match v.get("chargeInfoList") {
+            Some(Value::Array(array)) => array
+                .iter()
+                .map(|c| {
+                    Ok(ChargeInfo {
+                        rate: get_val!(c, "rate")?,
+                        charge_type: get_val!(c, "chargeType")?,
+                    })
+                })
+                .collect::<Result<Vec<ChargeInfo>>>()?,
+            _ => vec![],
+        };
+        Ok(Self {
+            id: get_val!(v, "id")?,
+            fee: get_val!(v, "fee")?,
+            st: get_val!(v, "st")?,
+            pl: get_val!(v, "pl").unwrap_or_default(),
+            dl: get_val!(v, "dl").unwrap_or_default(),
+            maxbr: get_val!(v, "maxbr").unwrap_or_default(),
+            max_level: level("maxBrLevel"),
+            play_level: level("plLevel"),
+            download_level: level("dlLevel"),
+            sp: get_val!(v, "sp").unwrap_or_default(),
+            cp: get_val!(v, "cp").unwrap_or_default(),
+            subp: get_val!(v, "subp").unwrap_or_default(),
+            charge_info,
+        })
+    }
+
+    /// 当前账号是否可播放
+    pub fn playable(&self) -> bool {
+        self.st >= 0 && self.pl > 0
+    }
+
+    /// 当前账号是否可下载
+    pub fn downloadable(&self) -> bool {
+        self.st >= 0 && self.dl > 0
+    }
+
+    pub fn copyright(&self) -> SongCopyright {
+        SongCopyright::from_status(self.st, self.fee, self.pl)
+    }
+}
+
+/// 从歌曲数据中解析权限
+fn privilege_of(v: &Value) -> Option<Privilege> {
+    v.get("privilege")
+        .and_then(|p| Privilege::from_value(p).ok())
+}
+
+/// 歌曲不可用原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SongUnavailable {
+    /// 需要 VIP
+    VipRequired,
+    /// 需要单独购买
+    PaymentRequired,
+    /// 无版权
+    NoCopyright,
+    /// 仅可试听
+    TrialOnly,
+    /// 该音质不可用
+    QualityUnavailable,
+    Unknown,
+}
+
+impl SongUnavailable {
+    /// 根据歌曲 URL 接口返回的单条数据判断不可用原因
+    /// 可正常播放时返回 None
+    pub fn from_url_entry(v: &Value) -> Option<Self> {
+        let url: String = get_val!(v, "url").unwrap_or_default();
+        let code: i32 = get_val!(v, "code").unwrap_or_default();
+        let fee: i32 = get_val!(v, "fee").unwrap_or_default();
+        if !url.is_empty() {
+            let trial = v.get("freeTrialInfo").is_some_and(|t| !t.is_null());
+            return trial.then_some(Self::TrialOnly);
+        }
+        let res = match (code, fee) {
+            (404, _) => Self::NoCopyright,
+            (_, 1) | (_, 8) => Self::VipRequired,
+            (_, 4) => Self::PaymentRequired,
+            (200, _) => Self::QualityUnavailable,
+            _ => Self::Unknown,
+        };
+        Some(res)
+    }
+}
+
+/// 按音质阶梯获取的歌曲 URL
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResolvedSongUrl {
+    /// 歌曲 id
+    pub id: u64,
+    /// 歌曲 URL，仅可试听时为试听片段
+    pub url: Option<SongUrl>,
+    /// 音质状态
+    pub quality: SongQualityState,
+    /// 未能获取首选音质的原因
+    pub reason: Option<SongUnavailable>,
+}
+
 /// 音质状态
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct SongQualityState {
@@ -309,7 +588,7 @@ fn default_quality_state() -> SongQualityState {
 }
 
 /// 歌曲信息
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct SongInfo {
     /// 歌曲 id
     pub id: u64,
@@ -331,6 +610,21 @@ pub struct SongInfo {
     pub quality: SongQualityState,
 
     pub copyright: SongCopyright,
+    /// 权限
+    #[serde(default)]
+    pub privilege: Option<Privilege>,
+    /// 全部歌手
+    #[serde(default)]
+    pub artists: Vec<String>,
+    /// 碟片号 (0 表示未知)
+    #[serde(default)]
+    pub disc: u32,
+    /// 音轨号 (0 表示未知)
+    #[serde(default)]
+    pub no: u32,
+    /// MV id (0 表示无 MV)
+    #[serde(default)]
+    pub mv: u64,
 }
 
 impl PartialEq for SongInfo {
@@ -372,6 +666,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -390,6 +689,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: v.get("simpleSong").and_then(privilege_of),
+                        artists: v.get("simpleSong").map_or_else(Vec::new, artists_of),
+                        disc: v.get("simpleSong").map_or(0, disc_of),
+                        no: v.get("simpleSong").map_or(0, no_of),
+                        mv: v.get("simpleSong").map_or(0, mv_of),
                     });
                 }
             }
@@ -410,6 +714,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -430,6 +739,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -450,6 +764,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -471,6 +790,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -488,6 +812,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -508,6 +837,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v),
+                        artists: artists_of(v),
+                        disc: disc_of(v),
+                        no: no_of(v),
+                        mv: mv_of(v),
                     });
                 }
             }
@@ -518,7 +852,10 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                     vec.push(SongInfo {
                         id: get_val!(v, "mainTrackId")?,
                         name: get_val!(v, "name")?,
-                        singer: format!("第 {} 期", num),
+                        singer: format!(
+                            "第 {} 期",
+                            get_val!(@as u64, v, "serialNum").unwrap_or(num as u64)
+                        ),
                         album: get_val!(@as u64, v, "createTime")?.to_string(),
                         album_id: 0,
                         pic_url: get_val!(v, "coverUrl")?,
@@ -526,6 +863,11 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: v.get("mainSong").and_then(privilege_of),
+                        artists: v.get("mainSong").map_or_else(Vec::new, artists_of),
+                        disc: v.get("mainSong").map_or(0, disc_of),
+                        no: v.get("mainSong").map_or(0, no_of),
+                        mv: v.get("mainSong").map_or(0, mv_of),
                     });
                     num -= 1;
                 }
@@ -548,16 +890,200 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         song_url: String::new(),
                         quality: default_quality_state(),
                         copyright: SongCopyright::Unknown,
+                        privilege: privilege_of(v)
+                            .or_else(|| v.get("songInfo").and_then(privilege_of)),
+                        artists: v.get("songInfo").map_or_else(Vec::new, artists_of),
+                        disc: v.get("songInfo").map_or(0, disc_of),
+                        no: v.get("songInfo").map_or(0, no_of),
+                        mv: v.get("songInfo").map_or(0, mv_of),
                     });
                 }
             }
             _ => {}
         }
+        if let Some(Value::Array(array)) = value.get("privileges") {
+            for p in array.iter().filter_map(|p| Privilege::from_value(p).ok()) {
+                if let Some(song) = vec
+                    .iter_mut()
+                    .find(|s| s.id == p.id && s.privilege.is_none())
+                {
+                    song.privilege = Some(p);
+                }
+            }
+        }
+        for song in vec.iter_mut() {
+            if let Some(p) = &song.privilege {
+                song.copyright = p.copyright();
+            }
+        }
         return Ok(vec);
     }
     Err(anyhow!("none"))
 }
 
+/// 歌曲的 MV id
+fn mv_of(v: &Value) -> u64 {
+    get_val!(v, "mv")
+        .or_else(|_| get_val!(v, "mvid"))
+        .unwrap_or_default()
+}
+
+/// 歌曲的全部歌手名
+fn artists_of(v: &Value) -> Vec<String> {
+    get_val!(@as &Vec<Value>, v, "ar")
+        .or_else(|_| get_val!(@as &Vec<Value>, v, "artists"))
+        .map(|a| a.iter().filter_map(|v| get_val!(v, "name").ok()).collect())
+        .unwrap_or_default()
+}
+
+/// 歌曲的碟片号
+fn disc_of(v: &Value) -> u32 {
+    get_val!(@as &Value, v, "cd")
+        .or_else(|_| get_val!(@as &Value, v, "disc"))
+        .and_then(u64_lossy)
+        .map_or(0, |d| d as u32)
+}
+
+/// 歌曲的音轨号
+fn no_of(v: &Value) -> u32 {
+    get_val!(v, "no").unwrap_or_default()
+}
+
+/// 解析常见格式 (ar/al/dt) 的歌曲数据
+fn to_song(v: &Value) -> Result<SongInfo> {
+    let unk = "unknown".to_string();
+    let privilege = privilege_of(v);
+    Ok(SongInfo {
+        id: get_val!(v, "id")?,
+        name: get_val!(v, "name")?,
+        singer: get_val!(@as &Vec<Value>, v, "ar")?
+            .first()
+            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
+            .unwrap_or_else(|| unk.clone()),
+        album: get_val!(v, "al", "name").unwrap_or_else(|_| unk.clone()),
+        album_id: get_val!(v, "al", "id")?,
+        pic_url: get_val!(v, "al", "picUrl").unwrap_or_default(),
+        duration: get_val!(v, "dt")?,
+        song_url: String::new(),
+        quality: default_quality_state(),
+        copyright: privilege
+            .as_ref()
+            .map_or(SongCopyright::Unknown, Privilege::copyright),
+        privilege,
+        artists: artists_of(v),
+        disc: disc_of(v),
+        no: no_of(v),
+        mv: mv_of(v),
+    })
+}
+
+/// 听歌排行时间范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayRecordRange {
+    /// 最近一周
+    Week,
+    /// 所有时间
+    All,
+}
+
+/// 听歌排行
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlayRecord {
+    /// 播放次数
+    pub play_count: u64,
+    /// 排行分数
+    pub score: u32,
+    /// 歌曲
+    pub song: SongInfo,
+}
+
+#[allow(unused)]
+pub fn to_play_record(json: String, range: PlayRecordRange) -> Result<Vec<PlayRecord>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let key = match range {
+            PlayRecordRange::Week => "weekData",
+            PlayRecordRange::All => "allData",
+        };
+        let array: &Vec<Value> = get_val!(value, key)?;
+        let mut vec = Vec::new();
+        for v in array.iter() {
+            vec.push(PlayRecord {
+                play_count: get_val!(v, "playCount")?,
+                score: get_val!(v, "score")?,
+                song: to_song(get_val!(v, "song")?)?,
+            });
+        }
+        return Ok(vec);
+    }
+    Err(anyhow!("get play record err!"))
+}
+
+/// 最近播放
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecentPlayed<T> {
+    /// 播放时间
+    pub play_time: u64,
+    /// 播放内容
+    pub item: T,
+}
+
+#[allow(unused)]
+pub fn to_recent_songs(json: String) -> Result<Vec<RecentPlayed<SongInfo>>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "data", "list")?;
+        let mut vec = Vec::new();
+        for v in array.iter() {
+            vec.push(RecentPlayed {
+                play_time: get_val!(v, "playTime")?,
+                item: to_song(get_val!(v, "data")?)?,
+            });
+        }
+        return Ok(vec);
+    }
+    Err(anyhow!("get recent songs err!"))
+}
+
+#[allow(unused)]
+pub fn to_recent_song_list(json: String, parse: Parse) -> Result<Vec<RecentPlayed<SongList>>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "data", "list")?;
+        let mut vec = Vec::new();
+        for v in array.iter() {
+            let d: &Value = get_val!(v, "data")?;
+            let item = match parse {
+                Parse::LikeAlbum => SongList {
+                    id: get_val!(d, "id")?,
+                    name: get_val!(d, "name")?,
+                    cover_img_url: get_val!(d, "picUrl")?,
+                    author: get_val!(@as &Vec<Value>, d, "artists")?
+                        .first()
+                        .map_or(Ok(String::new()), |v: &Value| get_val!(v, "name"))?,
+                    special_type: 0,
+                },
+                _ => SongList {
+                    id: get_val!(d, "id")?,
+                    name: get_val!(d, "name")?,
+                    cover_img_url: get_val!(d, "coverImgUrl")?,
+                    author: get_val!(d, "creator", "nickname").unwrap_or_default(),
+                    special_type: get_val!(d, "specialType").unwrap_or(0),
+                },
+            };
+            vec.push(RecentPlayed {
+                play_time: get_val!(v, "playTime")?,
+                item,
+            });
+        }
+        return Ok(vec);
+    }
+    Err(anyhow!("get recent list err!"))
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlayListDetail {
     pub id: u64,
@@ -568,6 +1094,35 @@ pub struct PlayListDetail {
 
     pub track_update_time: u64,
 
+    /// 创建者
+    #[serde(default)]
+    pub creator: UserProfile,
+    /// 标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 歌曲数
+    #[serde(default)]
+    pub track_count: u64,
+    /// 播放数
+    #[serde(default)]
+    pub play_count: u64,
+    /// 收藏数
+    #[serde(default)]
+    pub subscribed_count: u64,
+    /// 隐私设置
+    /// 0: 公开
+    /// 10: 隐私
+    #[serde(default)]
+    pub privacy: i32,
+    /// 歌单类型
+    /// 5: 我喜欢的音乐
+    /// 200: 视频歌单
+    #[serde(default)]
+    pub special_type: u32,
+    /// 是否已收藏
+    #[serde(default)]
+    pub subscribed: bool,
+
     pub songs: Vec<SongInfo>,
 }
 
@@ -602,6 +1157,11 @@ pub fn to_mix_detail(json: &Value) -> Result<PlayListDetail> {
                 song_url: String::new(),
                 quality: default_quality_state(),
                 copyright: SongCopyright::from_privilege(p)?,
+                privilege: Privilege::from_value(p).ok(),
+                artists: artists_of(v),
+                disc: disc_of(v),
+                no: no_of(v),
+                mv: mv_of(v),
             });
         }
 
@@ -613,6 +1173,24 @@ pub fn to_mix_detail(json: &Value) -> Result<PlayListDetail> {
             create_time: get_val!(value, "playlist", "createTime")?,
             track_update_time: get_val!(value, "playlist", "trackUpdateTime")?,
 
+            creator: get_val!(@as &Value, value, "playlist", "creator")
+                .ok()
+                .and_then(|v| to_profile(v).ok())
+                .unwrap_or_default(),
+            tags: get_val!(@as &Vec<Value>, value, "playlist", "tags")
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|t| t.as_str().map(str::to_owned))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            track_count: get_val!(value, "playlist", "trackCount").unwrap_or_default(),
+            play_count: get_val!(value, "playlist", "playCount").unwrap_or_default(),
+            subscribed_count: get_val!(value, "playlist", "subscribedCount").unwrap_or_default(),
+            privacy: get_val!(value, "playlist", "privacy").unwrap_or_default(),
+            special_type: get_val!(value, "playlist", "specialType").unwrap_or_default(),
+            subscribed: get_val!(value, "playlist", "subscribed").unwrap_or_default(),
+
             songs,
         });
     }
@@ -631,9 +1209,44 @@ pub struct AlbumDetail {
     pub artist_name: String,
     pub artist_pic_url: String,
 
+    /// 全部歌手
+    #[serde(default)]
+    pub artists: Vec<SingerInfo>,
+    /// 发行公司
+    #[serde(default)]
+    pub company: String,
+    /// 专辑类型，如 专辑, EP/Single
+    #[serde(default)]
+    pub album_type: String,
+    /// 专辑子类型，如 录音室版, 现场版
+    #[serde(default)]
+    pub sub_type: String,
+    /// 歌曲数
+    #[serde(default)]
+    pub size: u32,
+    /// 别名
+    #[serde(default)]
+    pub alias: Vec<String>,
+    /// 是否在售
+    #[serde(default)]
+    pub on_sale: bool,
+    /// 是否已购买 (数字专辑)
+    #[serde(default)]
+    pub paid: bool,
+
     pub songs: Vec<SongInfo>,
 }
 
+impl AlbumDetail {
+    /// 歌曲的碟片号和音轨号，缺少音轨信息时按歌曲顺序推算
+    pub fn position(&self, song_id: u64) -> Option<(u32, u32)> {
+        let i = self.songs.iter().position(|s| s.id == song_id)?;
+        let song = &self.songs[i];
+        let no = if song.no == 0 { i as u32 + 1 } else { song.no };
+        Some((song.disc.max(1), no))
+    }
+}
+
 #[allow(unused)]
 pub fn to_album_detail(json: &Value) -> Result<AlbumDetail> {
     let value = json;
@@ -656,11 +1269,19 @@ pub fn to_album_detail(json: &Value) -> Result<AlbumDetail> {
                     .unwrap_or_else(|| unk.clone()),
                 album: name.clone(),
                 album_id: id,
-                pic_url: pic_url.clone(),
+                pic_url: get_val!(v, "al", "picUrl")
+                    .ok()
+                    .filter(|u: &String| !u.is_empty())
+                    .unwrap_or_else(|| pic_url.clone()),
                 duration: get_val!(v, "dt")?,
                 song_url: String::new(),
                 quality: default_quality_state(),
                 copyright: SongCopyright::from_privilege(get_val!(v, "privilege")?)?,
+                privilege: privilege_of(v),
+                artists: artists_of(v),
+                disc: disc_of(v),
+                no: no_of(v),
+                mv: mv_of(v),
             });
         }
 
@@ -675,6 +1296,33 @@ pub fn to_album_detail(json: &Value) -> Result<AlbumDetail> {
             artist_name: get_val!(value, "album", "artist", "name")?,
             artist_pic_url: get_val!(value, "album", "artist", "picUrl")?,
 
+            artists: get_val!(@as &Vec<Value>, value, "album", "artists")
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| {
+                            Some(SingerInfo {
+                                id: get_val!(v, "id").ok()?,
+                                name: get_val!(v, "name").ok()?,
+                                pic_url: get_val!(v, "picUrl").unwrap_or_default(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            company: get_val!(value, "album", "company").unwrap_or_default(),
+            album_type: get_val!(value, "album", "type").unwrap_or_default(),
+            sub_type: get_val!(value, "album", "subType").unwrap_or_default(),
+            size: get_val!(value, "album", "size").unwrap_or_default(),
+            alias: get_val!(@as &Vec<Value>, value, "album", "alias")
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|t| t.as_str().map(str::to_owned))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            on_sale: get_val!(value, "album", "onSale").unwrap_or_default(),
+            paid: get_val!(value, "album", "paid").unwrap_or_default(),
+
             songs,
         });
     }
@@ -740,6 +1388,31 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                     });
                 }
             }
+            Parse::AlbumProduct => {
+                let array: &Vec<Value> = get_val!(@as &Vec<Value>, &value, "albumProducts")
+                    .or_else(|_| get_val!(&value, "data", "albumProducts"))?;
+                for v in array.iter() {
+                    vec.push(SongList {
+                        id: get_val!(v, "albumId")?,
+                        name: get_val!(v, "albumName")?,
+                        cover_img_url: get_val!(v, "coverUrl").unwrap_or_default(),
+                        author: get_val!(v, "artistName").unwrap_or_default(),
+                        special_type: 0,
+                    });
+                }
+            }
+            Parse::PaidAlbum => {
+                let array: &Vec<Value> = get_val!(&value, "paidAlbums")?;
+                for v in array.iter() {
+                    vec.push(SongList {
+                        id: get_val!(v, "id")?,
+                        name: get_val!(v, "name")?,
+                        cover_img_url: get_val!(v, "picUrl").unwrap_or_default(),
+                        author: get_val!(v, "artistName").unwrap_or_default(),
+                        special_type: 0,
+                    });
+                }
+            }
             Parse::Top => {
                 let array: &Vec<Value> = get_val!(&value, "playlists")?;
                 for v in array.iter() {
@@ -809,6 +1482,17 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
     Err(anyhow!("none"))
 }
 
+#[allow(unused)]
+pub fn to_privileges(json: String) -> Result<Vec<Privilege>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "data")?;
+        return array.iter().map(Privilege::from_value).collect();
+    }
+    Err(anyhow!("none"))
+}
+
 #[allow(unused)]
 pub fn to_song_id_list(json: String) -> Result<Vec<u64>> {
     let value = &serde_json::from_str::<Value>(&json)?;
@@ -901,98 +1585,1693 @@ pub fn to_login_info(json: String) -> Result<LoginInfo> {
     })
 }
 
-/// 轮播信息
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct BannersInfo {
-    /// 轮播图
-    pub pic: String,
-    /// 内容 id
-    pub target_id: u64,
-    /// 内容类型
-    pub target_type: TargetType,
+/// 用户信息
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UserProfile {
+    /// 用户 id
+    pub uid: u64,
+    /// 用户昵称
+    pub nickname: String,
+    /// 用户头像
+    pub avatar_url: String,
+    /// 个人签名
+    pub signature: String,
+    /// 性别
+    /// 0: 未知
+    /// 1: 男
+    /// 2: 女
+    pub gender: i32,
+    /// 省份代码
+    pub province: u64,
+    /// 城市代码
+    pub city: u64,
+    /// 等级
+    pub level: u32,
+    /// 累计听歌数
+    pub listen_songs: u64,
+    /// VIP 等级
+    pub vip_type: i32,
+    /// 创建的歌单数
+    pub playlist_count: u64,
+    /// 歌单被收藏数
+    pub playlist_subscribed_count: u64,
+    /// 关注数
+    pub follows: u64,
+    /// 粉丝数
+    pub followeds: u64,
+    /// 是否已关注
+    pub followed: bool,
+}
+
+/// 解析用户信息，缺少的字段使用默认值
+fn to_profile(v: &Value) -> Result<UserProfile> {
+    Ok(UserProfile {
+        uid: get_val!(v, "userId")?,
+        nickname: get_val!(v, "nickname")?,
+        avatar_url: get_val!(v, "avatarUrl").unwrap_or_default(),
+        signature: get_val!(v, "signature").unwrap_or_default(),
+        gender: get_val!(v, "gender").unwrap_or_default(),
+        province: get_val!(v, "province").unwrap_or_default(),
+        city: get_val!(v, "city").unwrap_or_default(),
+        level: 0,
+        listen_songs: 0,
+        vip_type: get_val!(v, "vipType").unwrap_or_default(),
+        playlist_count: get_val!(v, "playlistCount").unwrap_or_default(),
+        playlist_subscribed_count: get_val!(v, "playlistBeSubscribedCount").unwrap_or_default(),
+        follows: get_val!(v, "follows").unwrap_or_default(),
+        followeds: get_val!(v, "followeds").unwrap_or_default(),
+        followed: get_val!(v, "followed").unwrap_or_default(),
+    })
 }
 
 #[allow(unused)]
-pub fn to_banners_info(json: String) -> Result<Vec<BannersInfo>> {
+pub fn to_user_detail(json: String) -> Result<UserProfile> {
     let value = &serde_json::from_str::<Value>(&json)?;
     let code: i32 = get_val!(value, "code")?;
     if code == 200 {
-        let array: &Vec<Value> = get_val!(value, "banners")?;
-        let mut vec: Vec<BannersInfo> = Vec::new();
-        for v in array.iter() {
-            let bi: BannersInfo = BannersInfo {
-                pic: get_val!(v, "imageUrl")?,
-                target_id: get_val!(v, "targetId")?,
-                target_type: TargetType::from(get_val!(@as i32, v, "targetType")?),
-            };
-            vec.push(bi);
-        }
-        return Ok(vec);
+        let mut profile = to_profile(get_val!(value, "profile")?)?;
+        profile.level = get_val!(value, "level").unwrap_or_default();
+        profile.listen_songs = get_val!(value, "listenSongs").unwrap_or_default();
+        return Ok(profile);
     }
-    Err(anyhow!("none"))
+    Err(anyhow!("get user detail err!"))
 }
 
+/// 用户列表分页
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UserPage {
+    /// 用户列表
+    pub users: Vec<UserProfile>,
+    /// 是否还有更多
+    pub more: bool,
+    /// 下一页游标 (粉丝列表按此分页)，无数据时为 -1
+    pub lasttime: i64,
+}
+
+/// parse: 解析方式
 #[allow(unused)]
-pub fn to_captcha(json: String) -> Result<()> {
+pub fn to_user_list(json: String, parse: Parse) -> Result<UserPage> {
     let value = &serde_json::from_str::<Value>(&json)?;
     let code: i32 = get_val!(value, "code")?;
-    if code.eq(&200) {
-        return Ok(());
-    }
-    let data: bool = get_val!(value, "data")?;
-    if data {
-        return Ok(());
+    if code == 200 {
+        let array: &Vec<Value> = match parse {
+            Parse::Follows => get_val!(value, "follow")?,
+            Parse::Followeds => get_val!(value, "followeds")?,
+            Parse::Subscribers => get_val!(value, "subscribers")?,
+            Parse::Search => get_val!(value, "result", "userprofiles")?,
+            _ => return Err(anyhow!("unsupported user list parse: {:?}", parse)),
+        };
+        return Ok(UserPage {
+            users: array.iter().map(to_profile).collect::<Result<_>>()?,
+            more: get_val!(value, "more")
+                .or_else(|_| get_val!(value, "hasMore"))
+                .unwrap_or_default(),
+            lasttime: get_val!(value, "lasttime")
+                .or_else(|_| match array.last() {
+                    Some(v) => get_val!(v, "time"),
+                    None => Err(anyhow!("empty user list")),
+                })
+                .unwrap_or(-1),
+        });
     }
-    Err(anyhow!("get captcha err!"))
+    Err(anyhow!("get user list err!"))
 }
 
-#[allow(unused)]
-pub fn to_unikey(json: String) -> Result<String> {
-    let value = &serde_json::from_str::<Value>(&json)?;
-    let code: i32 = get_val!(value, "code")?;
+/// 会员信息
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct VipMembership {
+    /// 会员代码
+    pub vip_code: i32,
+    /// 到期时间(毫秒)
+    pub expire_time: u64,
+}
 
-    if code.eq(&200) {
-        let unikey: String = get_val!(value, "unikey")?;
-        return Ok(unikey);
+impl VipMembership {
+    fn from_value(v: &Value) -> Option<Self> {
+        let membership = Self {
+            vip_code: get_val!(v, "vipCode").unwrap_or_default(),
+            expire_time: get_val!(v, "expireTime").unwrap_or_default(),
+        };
+        (membership.vip_code != 0 || membership.expire_time != 0).then_some(membership)
+    }
+
+    /// 是否在有效期内
+    pub fn is_active(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        self.expire_time > now
     }
-    Err(anyhow!("get unikey err!"))
 }
 
-/// 轮播信息
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// VIP 状态
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct VipStatus {
+    /// 黑胶 VIP
+    pub black_vinyl: Option<VipMembership>,
+    /// 音乐包
+    pub music_package: Option<VipMembership>,
+    /// 黑胶 SVIP
+    pub svip: Option<VipMembership>,
+    /// 黑胶等级
+    pub level: i32,
+}
+
+impl VipStatus {
+    fn active(membership: &Option<VipMembership>) -> bool {
+        membership.as_ref().is_some_and(VipMembership::is_active)
+    }
+
+    /// 是否为有效的黑胶 VIP (含 SVIP)
+    pub fn is_black_vinyl(&self) -> bool {
+        Self::active(&self.black_vinyl) || Self::active(&self.svip)
+    }
+
+    /// 是否为有效的 SVIP
+    pub fn is_svip(&self) -> bool {
+        Self::active(&self.svip)
+    }
+
+    /// 可播放的最高音质
+    /// SVIP: 超清母带
+    /// 黑胶 VIP: 高清臻音
+    /// 其他: 极高
+    pub fn max_quality(&self) -> SongQuality {
+        if self.is_svip() {
+            SongQuality::Master
+        } else if self.is_black_vinyl() {
+            SongQuality::HiRes
+        } else {
+            SongQuality::Extreme
+        }
+    }
+}
+
+#[allow(unused)]
+pub fn to_vip_status(json: String) -> Result<VipStatus> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let data: &Value = get_val!(value, "data")?;
+        return Ok(VipStatus {
+            black_vinyl: VipMembership::from_value(&data["associator"]),
+            music_package: VipMembership::from_value(&data["musicPackage"]),
+            svip: VipMembership::from_value(&data["redplus"]),
+            level: get_val!(data, "redVipLevel").unwrap_or_default(),
+        });
+    }
+    Err(anyhow!("get vip info err!"))
+}
+
+/// 用户等级
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UserLevel {
+    /// 当前等级
+    pub level: u32,
+    /// 升级进度 (0~1)
+    pub progress: f64,
+    /// 当前听歌数
+    pub now_play_count: u64,
+    /// 升级所需听歌数
+    pub next_play_count: u64,
+    /// 当前登录天数
+    pub now_login_count: u64,
+    /// 升级所需登录天数
+    pub next_login_count: u64,
+}
+
+#[allow(unused)]
+pub fn to_user_level(json: String) -> Result<UserLevel> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let data: &Value = get_val!(value, "data")?;
+        return Ok(UserLevel {
+            level: get_val!(data, "level")?,
+            progress: get_val!(data, "progress").unwrap_or_default(),
+            now_play_count: get_val!(data, "nowPlayCount").unwrap_or_default(),
+            next_play_count: get_val!(data, "nextPlayCount").unwrap_or_default(),
+            now_login_count: get_val!(data, "nowLoginCount").unwrap_or_default(),
+            next_login_count: get_val!(data, "nextLoginCount").unwrap_or_default(),
+        });
+    }
+    Err(anyhow!("get user level err!"))
+}
+
+/// 收藏数量
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SubCount {
+    /// 创建的歌单数
+    pub created_playlist: u64,
+    /// 收藏的歌单数
+    pub subscribed_playlist: u64,
+    /// 收藏的歌手数
+    pub artist: u64,
+    /// 收藏的 MV 数
+    pub mv: u64,
+    /// 订阅的电台数
+    pub dj_radio: u64,
+    /// 创建的电台数
+    pub created_dj_radio: u64,
+}
+
+#[allow(unused)]
+pub fn to_sub_count(json: String) -> Result<SubCount> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(SubCount {
+            created_playlist: get_val!(value, "createdPlaylistCount").unwrap_or_default(),
+            subscribed_playlist: get_val!(value, "subPlaylistCount").unwrap_or_default(),
+            artist: get_val!(value, "artistCount").unwrap_or_default(),
+            mv: get_val!(value, "mvCount").unwrap_or_default(),
+            dj_radio: get_val!(value, "djRadioCount").unwrap_or_default(),
+            created_dj_radio: get_val!(value, "createDjRadioCount").unwrap_or_default(),
+        });
+    }
+    Err(anyhow!("get sub count err!"))
+}
+
+/// 账号信息
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AccountInfo {
+    /// 用户信息
+    pub profile: UserProfile,
+    /// VIP 状态
+    pub vip: VipStatus,
+    /// 用户等级
+    pub level: UserLevel,
+    /// 收藏数量
+    pub sub_count: SubCount,
+}
+
+/// 搜索类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SearchType {
+    Song,
+    Album,
+    Singer,
+    SongList,
+    User,
+    Mv,
+    Lyrics,
+    Radio,
+    Video,
+}
+
+impl SearchType {
+    /// 接口使用的类型代码
+    pub fn as_code(self) -> u32 {
+        match self {
+            Self::Song => 1,
+            Self::Album => 10,
+            Self::Singer => 100,
+            Self::SongList => 1000,
+            Self::User => 1002,
+            Self::Mv => 1004,
+            Self::Lyrics => 1006,
+            Self::Radio => 1009,
+            Self::Video => 1014,
+        }
+    }
+
+    /// 结果列表及总数字段
+    fn keys(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Song | Self::Lyrics => ("songs", "songCount"),
+            Self::Album => ("albums", "albumCount"),
+            Self::Singer => ("artists", "artistCount"),
+            Self::SongList => ("playlists", "playlistCount"),
+            Self::User => ("userprofiles", "userprofileCount"),
+            Self::Mv => ("mvs", "mvCount"),
+            Self::Radio => ("djRadios", "djRadiosCount"),
+            Self::Video => ("videos", "videoCount"),
+        }
+    }
+}
+
+/// MV 信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MvInfo {
+    /// MV id
+    pub id: u64,
+    /// MV 名称
+    pub name: String,
+    /// 封面
+    pub cover: String,
+    /// 歌手
+    pub artist_name: String,
+    /// 时长(毫秒)
+    pub duration: u64,
+    /// 播放次数
+    pub play_count: u64,
+}
+
+fn to_mv_info(v: &Value) -> Result<MvInfo> {
+    Ok(MvInfo {
+        id: get_val!(v, "id")?,
+        name: get_val!(v, "name")?,
+        cover: get_val!(v, "cover")
+            .or_else(|_| get_val!(v, "imgurl16v9"))
+            .unwrap_or_default(),
+        artist_name: get_val!(v, "artistName")
+            .or_else(|_| get_val!(v, "artist", "name"))
+            .unwrap_or_default(),
+        duration: get_val!(v, "duration").unwrap_or_default(),
+        play_count: get_val!(v, "playCount").unwrap_or_default(),
+    })
+}
+
+#[allow(unused)]
+pub fn to_mv_list(json: String) -> Result<Vec<MvInfo>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "mvs")?;
+        return array.iter().map(to_mv_info).collect();
+    }
+    Err(anyhow!("get mv list err!"))
+}
+
+/// MV 详情
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MvDetail {
+    /// MV id
+    pub id: u64,
+    /// MV 名称
+    pub name: String,
+    /// 歌手 id
+    pub artist_id: u64,
+    /// 歌手
+    pub artist_name: String,
+    /// 描述
+    pub desc: String,
+    /// 封面
+    pub cover: String,
+    /// 时长(毫秒)
+    pub duration: u64,
+    /// 播放次数
+    pub play_count: u64,
+    /// 发布日期
+    pub publish_time: String,
+    /// 可用分辨率 (如 240/480/720/1080)
+    pub resolutions: Vec<u32>,
+    /// 是否已收藏
+    pub subscribed: bool,
+}
+
+#[allow(unused)]
+pub fn to_mv_detail(json: String) -> Result<MvDetail> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let data: &Value = get_val!(value, "data")?;
+        let mut resolutions: Vec<u32> = match &data["brs"] {
+            Value::Array(brs) => brs.iter().filter_map(|b| get_val!(b, "br").ok()).collect(),
+            // 旧版接口以分辨率为键
+            Value::Object(brs) => brs.keys().filter_map(|k| k.parse().ok()).collect(),
+            _ => vec![],
+        };
+        resolutions.sort_unstable();
+        return Ok(MvDetail {
+            id: get_val!(data, "id")?,
+            name: get_val!(data, "name")?,
+            artist_id: get_val!(data, "artistId").unwrap_or_default(),
+            artist_name: get_val!(data, "artistName").unwrap_or_default(),
+            desc: get_val!(data, "desc").unwrap_or_default(),
+            cover: get_val!(data, "cover").unwrap_or_default(),
+            duration: get_val!(data, "duration").unwrap_or_default(),
+            play_count: get_val!(data, "playCount").unwrap_or_default(),
+            publish_time: get_val!(data, "publishTime").unwrap_or_default(),
+            resolutions,
+            subscribed: get_val!(value, "subed").unwrap_or_default(),
+        });
+    }
+    Err(anyhow!("get mv detail err!"))
+}
+
+/// MV/视频播放地址
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VideoUrl {
+    /// MV/视频 id
+    pub id: String,
+    /// 播放地址
+    pub url: String,
+    /// 分辨率
+    pub resolution: u32,
+    /// 文件大小
+    pub size: u64,
+}
+
+fn to_video_url_value(v: &Value) -> Result<VideoUrl> {
+    let url: String = get_val!(v, "url").unwrap_or_default();
+    if url.is_empty() {
+        return Err(anyhow!("video url unavailable"));
+    }
+    Ok(VideoUrl {
+        id: id_string(get_val!(v, "id")?)?,
+        url,
+        resolution: get_val!(v, "r").unwrap_or_default(),
+        size: get_val!(v, "size").unwrap_or_default(),
+    })
+}
+
+#[allow(unused)]
+pub fn to_mv_url(json: String) -> Result<VideoUrl> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return to_video_url_value(get_val!(value, "data")?);
+    }
+    Err(anyhow!("get mv url err!"))
+}
+
+#[allow(unused)]
+pub fn to_video_url(json: String) -> Result<VideoUrl> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "urls")?;
+        return to_video_url_value(array.first().ok_or_else(|| anyhow!("none"))?);
+    }
+    Err(anyhow!("get video url err!"))
+}
+
+/// 视频详情
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VideoDetail {
+    /// 视频 id
+    pub id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+    /// 封面
+    pub cover_url: String,
+    /// 时长(毫秒)
+    pub duration: u64,
+    /// 播放次数
+    pub play_count: u64,
+    /// 作者
+    pub creator: String,
+    /// 发布时间(毫秒)
+    pub publish_time: u64,
+    /// 可用分辨率
+    pub resolutions: Vec<u32>,
+}
+
+#[allow(unused)]
+pub fn to_video_detail(json: String) -> Result<VideoDetail> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let data: &Value = get_val!(value, "data")?;
+        let mut resolutions: Vec<u32> = get_val!(@as &Vec<Value>, data, "resolutions")
+            .map(|r| {
+                r.iter()
+                    .filter_map(|r| get_val!(r, "resolution").ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        resolutions.sort_unstable();
+        return Ok(VideoDetail {
+            id: get_val!(data, "vid")?,
+            title: get_val!(data, "title")?,
+            description: get_val!(data, "description").unwrap_or_default(),
+            cover_url: get_val!(data, "coverUrl").unwrap_or_default(),
+            duration: get_val!(data, "durationms").unwrap_or_default(),
+            play_count: get_val!(data, "playTime").unwrap_or_default(),
+            creator: get_val!(data, "creator", "nickname").unwrap_or_default(),
+            publish_time: get_val!(data, "publishTime").unwrap_or_default(),
+            resolutions,
+        });
+    }
+    Err(anyhow!("get video detail err!"))
+}
+
+/// 视频信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VideoInfo {
+    /// 视频 id (MV 为数字 id)
+    pub id: String,
+    /// 标题
+    pub title: String,
+    /// 封面
+    pub cover_url: String,
+    /// 时长(毫秒)
+    pub duration: u64,
+    /// 播放次数
+    pub play_count: u64,
+    /// 作者
+    pub creator: String,
+    /// 是否为 MV
+    pub is_mv: bool,
+}
+
+fn to_video_info(v: &Value) -> Result<VideoInfo> {
+    Ok(VideoInfo {
+        id: id_string(get_val!(v, "vid")?)?,
+        title: get_val!(v, "title")?,
+        cover_url: get_val!(v, "coverUrl").unwrap_or_default(),
+        duration: get_val!(v, "durationms").unwrap_or_default(),
+        play_count: get_val!(v, "playTime").unwrap_or_default(),
+        creator: get_val!(@as &Vec<Value>, v, "creator")
+            .ok()
+            .and_then(|c| c.first())
+            .and_then(|c| get_val!(c, "userName").ok())
+            .unwrap_or_default(),
+        is_mv: get_val!(@as i32, v, "type").unwrap_or_default() == 0,
+    })
+}
+
+/// 电台
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Radio {
+    /// 电台 id
+    pub id: u64,
+    /// 电台名称
+    pub name: String,
+    /// 封面
+    pub pic_url: String,
+    /// 简介
+    pub desc: String,
+    /// 主播 id
+    pub dj_id: u64,
+    /// 主播
+    pub dj_name: String,
+    /// 主播头像
+    pub dj_avatar_url: String,
+    /// 分类 id
+    pub category_id: u64,
+    /// 分类
+    pub category: String,
+    /// 节目数
+    pub program_count: u64,
+    /// 订阅数
+    pub sub_count: u64,
+    /// 是否已订阅
+    pub subscribed: bool,
+    /// 创建时间(毫秒)
+    pub create_time: u64,
+    /// 最新节目名称
+    pub last_program_name: String,
+}
+
+fn to_radio(v: &Value) -> Result<Radio> {
+    Ok(Radio {
+        id: get_val!(v, "id")?,
+        name: get_val!(v, "name")?,
+        pic_url: get_val!(v, "picUrl").unwrap_or_default(),
+        desc: get_val!(v, "desc")
+            .or_else(|_| get_val!(v, "rcmdtext"))
+            .unwrap_or_default(),
+        dj_id: get_val!(v, "dj", "userId").unwrap_or_default(),
+        dj_name: get_val!(v, "dj", "nickname").unwrap_or_default(),
+        dj_avatar_url: get_val!(v, "dj", "avatarUrl").unwrap_or_default(),
+        category_id: get_val!(v, "categoryId").unwrap_or_default(),
+        category: get_val!(v, "category").unwrap_or_default(),
+        program_count: get_val!(v, "programCount").unwrap_or_default(),
+        sub_count: get_val!(v, "subCount").unwrap_or_default(),
+        subscribed: get_val!(v, "subed").unwrap_or_default(),
+        create_time: get_val!(v, "createTime").unwrap_or_default(),
+        last_program_name: get_val!(v, "lastProgramName").unwrap_or_default(),
+    })
+}
+
+#[allow(unused)]
+pub fn to_radio_detail(json: String) -> Result<Radio> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return to_radio(get_val!(value, "data")?);
+    }
+    Err(anyhow!("get radio detail err!"))
+}
+
+#[allow(unused)]
+pub fn to_radio_list(json: String) -> Result<Vec<Radio>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "djRadios")?;
+        return array.iter().map(to_radio).collect();
+    }
+    Err(anyhow!("get radio list err!"))
+}
+
+/// 电台分类
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RadioCategory {
+    /// 分类 id
+    pub id: u64,
+    /// 分类名称
+    pub name: String,
+    /// 分类图标
+    pub pic_url: String,
+}
+
+#[allow(unused)]
+pub fn to_radio_categories(json: String) -> Result<Vec<RadioCategory>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "categories")?;
+        return array
+            .iter()
+            .map(|v| {
+                Ok(RadioCategory {
+                    id: get_val!(v, "id")?,
+                    name: get_val!(v, "name")?,
+                    pic_url: get_val!(v, "pic84x84IdUrl")
+                        .or_else(|_| get_val!(v, "picWebUrl"))
+                        .unwrap_or_default(),
+                })
+            })
+            .collect();
+    }
+    Err(anyhow!("get radio categories err!"))
+}
+
+/// 电台节目
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RadioProgram {
+    /// 节目 id
+    pub id: u64,
+    /// 节目名称
+    pub name: String,
+    /// 节目简介
+    pub description: String,
+    /// 期数
+    pub serial_num: u32,
+    /// 封面
+    pub cover_url: String,
+    /// 时长(毫秒)
+    pub duration: u64,
+    /// 发布时间(毫秒)
+    pub create_time: u64,
+    /// 收听数
+    pub listener_count: u64,
+    /// 点赞数
+    pub liked_count: u64,
+    /// 电台 id
+    pub radio_id: u64,
+    /// 电台名称
+    pub radio_name: String,
+    /// 主播
+    pub dj_name: String,
+    /// 节目音频
+    pub song: SongInfo,
+}
+
+#[allow(unused)]
+pub fn to_radio_programs(json: String) -> Result<Vec<RadioProgram>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let array: &Vec<Value> = get_val!(value, "programs")?;
+    let songs = to_song_info(json, Parse::Radio)?;
+    array
+        .iter()
+        .zip(songs)
+        .map(|(v, song)| {
+            Ok(RadioProgram {
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                description: get_val!(v, "description").unwrap_or_default(),
+                serial_num: get_val!(v, "serialNum").unwrap_or_default(),
+                cover_url: get_val!(v, "coverUrl").unwrap_or_default(),
+                duration: get_val!(v, "duration").unwrap_or_default(),
+                create_time: get_val!(v, "createTime").unwrap_or_default(),
+                listener_count: get_val!(v, "listenerCount").unwrap_or_default(),
+                liked_count: get_val!(v, "likedCount").unwrap_or_default(),
+                radio_id: get_val!(v, "radio", "id").unwrap_or_default(),
+                radio_name: get_val!(v, "radio", "name").unwrap_or_default(),
+                dj_name: get_val!(v, "dj", "nickname").unwrap_or_default(),
+                song,
+            })
+        })
+        .collect()
+}
+
+/// 匹配的歌词片段
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LyricFragment {
+    /// 歌词行
+    pub text: String,
+    /// 高亮范围 (字符偏移，左闭右开)
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// 歌词搜索结果
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LyricMatch {
+    /// 歌曲信息
+    pub song: SongInfo,
+    /// 匹配的歌词片段
+    pub fragments: Vec<LyricFragment>,
+}
+
+/// 由全文及匹配范围提取命中的歌词行
+fn lyric_fragments(txt: &str, ranges: &[(usize, usize)]) -> Vec<LyricFragment> {
+    let mut fragments = Vec::new();
+    let mut line_start = 0;
+    for line in txt.split('\n') {
+        let line_end = line_start + line.chars().count();
+        let hits: Vec<(usize, usize)> = ranges
+            .iter()
+            .filter(|(start, end)| start < end && *start < line_end && *end > line_start)
+            .map(|(start, end)| {
+                (
+                    start.max(&line_start) - line_start,
+                    end.min(&line_end) - line_start,
+                )
+            })
+            .collect();
+        if !hits.is_empty() {
+            fragments.push(LyricFragment {
+                text: line.to_owned(),
+                ranges: hits,
+            });
+        }
+        line_start = line_end + 1;
+    }
+    fragments
+}
+
+/// 解析旧版以 <b></b> 标记高亮的歌词行
+fn lyric_fragment_tagged(line: &str) -> LyricFragment {
+    let mut text = String::new();
+    let mut ranges = Vec::new();
+    let mut rest = line;
+    let mut len = 0;
+    while let Some(start) = rest.find("<b>") {
+        text.push_str(&rest[..start]);
+        len += rest[..start].chars().count();
+        rest = &rest[start + 3..];
+        let end = rest.find("</b>").unwrap_or(rest.len());
+        let word = &rest[..end];
+        text.push_str(word);
+        ranges.push((len, len + word.chars().count()));
+        len += word.chars().count();
+        rest = rest.get(end + 4..).unwrap_or_default();
+    }
+    text.push_str(rest);
+    LyricFragment { text, ranges }
+}
+
+#[allow(unused)]
+pub fn to_lyric_matches(json: String) -> Result<Vec<LyricMatch>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(anyhow!("search lyrics err: {}", code));
+    }
+    let array: &Vec<Value> = get_val!(value, "result", "songs")?;
+    let fragments = array.iter().map(|v| match &v["lyrics"] {
+        Value::Object(_) => {
+            let txt: String = get_val!(v, "lyrics", "txt").unwrap_or_default();
+            let ranges: Vec<(usize, usize)> = get_val!(@as &Vec<Value>, v, "lyrics", "range")
+                .map(|r| {
+                    r.iter()
+                        .filter_map(|r| {
+                            Some((
+                                get_val!(@as u64, r, "first").ok()? as usize,
+                                get_val!(@as u64, r, "second").ok()? as usize,
+                            ))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            lyric_fragments(&txt, &ranges)
+        }
+        Value::Array(lines) => lines
+            .iter()
+            .filter_map(Value::as_str)
+            .map(lyric_fragment_tagged)
+            .collect(),
+        _ => vec![],
+    });
+    Ok(to_song_info(json, Parse::Search)?
+        .into_iter()
+        .zip(fragments)
+        .map(|(song, fragments)| LyricMatch { song, fragments })
+        .collect())
+}
+
+/// 搜索结果列表
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum SearchItems {
+    Songs(Vec<SongInfo>),
+    Albums(Vec<SongList>),
+    Singers(Vec<SingerInfo>),
+    SongLists(Vec<SongList>),
+    Users(Vec<UserProfile>),
+    Mvs(Vec<MvInfo>),
+    Lyrics(Vec<LyricMatch>),
+    Radios(Vec<Radio>),
+    Videos(Vec<VideoInfo>),
+}
+
+impl SearchItems {
+    fn empty(kind: SearchType) -> Self {
+        match kind {
+            SearchType::Song => Self::Songs(vec![]),
+            SearchType::Album => Self::Albums(vec![]),
+            SearchType::Singer => Self::Singers(vec![]),
+            SearchType::SongList => Self::SongLists(vec![]),
+            SearchType::User => Self::Users(vec![]),
+            SearchType::Mv => Self::Mvs(vec![]),
+            SearchType::Lyrics => Self::Lyrics(vec![]),
+            SearchType::Radio => Self::Radios(vec![]),
+            SearchType::Video => Self::Videos(vec![]),
+        }
+    }
+
+    /// 结果数量
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Songs(v) => v.len(),
+            Self::Lyrics(v) => v.len(),
+            Self::Albums(v) | Self::SongLists(v) => v.len(),
+            Self::Singers(v) => v.len(),
+            Self::Users(v) => v.len(),
+            Self::Mvs(v) => v.len(),
+            Self::Radios(v) => v.len(),
+            Self::Videos(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 搜索结果
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchResult {
+    /// 结果总数
+    pub total: u64,
+    /// 是否还有更多
+    pub has_more: bool,
+    /// 结果列表
+    pub items: SearchItems,
+}
+
+/// kind: 搜索类型
+/// offset: 请求的起始点，用于计算是否还有更多
+#[allow(unused)]
+pub fn to_search_result(json: String, kind: SearchType, offset: u16) -> Result<SearchResult> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(anyhow!("search err!"));
+    }
+    let (list_key, count_key) = kind.keys();
+    let total: u64 = get_val!(value, "result", count_key).unwrap_or_default();
+    let array: &Vec<Value> = match get_val!(value, "result", list_key) {
+        Result::Ok(array) => array,
+        // 无结果时不返回列表
+        Err(_) => &Vec::new(),
+    };
+    let len = array.len() as u64;
+    let items = if array.is_empty() {
+        SearchItems::empty(kind)
+    } else {
+        match kind {
+            SearchType::Song => SearchItems::Songs(to_song_info(json, Parse::Search)?),
+            SearchType::Lyrics => SearchItems::Lyrics(to_lyric_matches(json)?),
+            SearchType::Album => SearchItems::Albums(to_song_list(json, Parse::SearchAlbum)?),
+            SearchType::Singer => SearchItems::Singers(to_singer_info(json)?),
+            SearchType::SongList => SearchItems::SongLists(to_song_list(json, Parse::Search)?),
+            SearchType::User => SearchItems::Users(to_user_list(json, Parse::Search)?.users),
+            SearchType::Mv => {
+                SearchItems::Mvs(array.iter().map(to_mv_info).collect::<Result<_>>()?)
+            }
+            SearchType::Radio => {
+                SearchItems::Radios(array.iter().map(to_radio).collect::<Result<_>>()?)
+            }
+            SearchType::Video => {
+                SearchItems::Videos(array.iter().map(to_video_info).collect::<Result<_>>()?)
+            }
+        }
+    };
+    Ok(SearchResult {
+        total,
+        has_more: get_val!(value, "result", "hasMore")
+            .unwrap_or_else(|_| u64::from(offset) + len < total),
+        items,
+    })
+}
+
+/// 搜索建议
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchSuggest {
+    /// 类型
+    pub kind: SearchType,
+    /// id
+    pub id: u64,
+    /// 名称
+    pub name: String,
+    /// 附加信息 (歌手/创建者)
+    pub subtitle: String,
+}
+
+/// 按接口返回的顺序解析搜索建议
+#[allow(unused)]
+pub fn to_search_suggest(json: String) -> Result<Vec<SearchSuggest>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(anyhow!("search suggest err!"));
+    }
+    let mut vec = Vec::new();
+    let result = &value["result"];
+    let order: &Vec<Value> = match get_val!(result, "order") {
+        Result::Ok(order) => order,
+        Err(_) => return Ok(vec),
+    };
+    for key in order.iter().filter_map(Value::as_str) {
+        let kind = match key {
+            "songs" => SearchType::Song,
+            "albums" => SearchType::Album,
+            "artists" => SearchType::Singer,
+            "playlists" => SearchType::SongList,
+            "mvs" => SearchType::Mv,
+            _ => continue,
+        };
+        let array: &Vec<Value> = get_val!(result, key)?;
+        for v in array.iter() {
+            let subtitle = match kind {
+                SearchType::Song => get_val!(@as &Vec<Value>, v, "artists")
+                    .ok()
+                    .and_then(|a| a.first())
+                    .and_then(|a| get_val!(a, "name").ok()),
+                SearchType::Album => get_val!(v, "artist", "name").ok(),
+                SearchType::Mv => get_val!(v, "artistName").ok(),
+                _ => None,
+            };
+            vec.push(SearchSuggest {
+                kind,
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                subtitle: subtitle.unwrap_or_default(),
+            });
+        }
+    }
+    Ok(vec)
+}
+
+/// 搜索多重匹配 (最佳匹配的歌手/专辑)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MultiMatch {
+    /// 歌手
+    pub singers: Vec<SingerInfo>,
+    /// 专辑
+    pub albums: Vec<SongList>,
+}
+
+#[allow(unused)]
+pub fn to_multi_match(json: String) -> Result<MultiMatch> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(anyhow!("search multimatch err!"));
+    }
+    let mut multi = MultiMatch::default();
+    if let Result::Ok(array) = get_val!(@as &Vec<Value>, value, "result", "artist") {
+        for v in array.iter() {
+            multi.singers.push(SingerInfo {
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                pic_url: get_val!(v, "picUrl")
+                    .or_else(|_| get_val!(v, "img1v1Url"))
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    if let Result::Ok(array) = get_val!(@as &Vec<Value>, value, "result", "album") {
+        for v in array.iter() {
+            multi.albums.push(SongList {
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                cover_img_url: get_val!(v, "picUrl").unwrap_or_default(),
+                author: get_val!(v, "artist", "name").unwrap_or_default(),
+                special_type: 0,
+            });
+        }
+    }
+    Ok(multi)
+}
+
+/// 热搜
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HotSearch {
+    /// 搜索词
+    pub word: String,
+    /// 热度
+    pub score: u64,
+    /// 描述
+    pub content: String,
+    /// 图标类型
+    /// 0: 无
+    /// 1: 热
+    /// 2: 新
+    /// 5: 上升
+    pub icon_type: i32,
+}
+
+/// 按排名顺序解析热搜列表
+#[allow(unused)]
+pub fn to_hot_search(json: String) -> Result<Vec<HotSearch>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "data")?;
+        return array
+            .iter()
+            .map(|v| {
+                Ok(HotSearch {
+                    word: get_val!(v, "searchWord")?,
+                    score: get_val!(v, "score").unwrap_or_default(),
+                    content: get_val!(v, "content").unwrap_or_default(),
+                    icon_type: get_val!(v, "iconType").unwrap_or_default(),
+                })
+            })
+            .collect();
+    }
+    Err(anyhow!("get hot search err!"))
+}
+
+/// 默认搜索关键词
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DefaultKeyword {
+    /// 搜索框显示的关键词
+    pub show_keyword: String,
+    /// 实际搜索的关键词
+    pub real_keyword: String,
+}
+
+#[allow(unused)]
+pub fn to_default_keyword(json: String) -> Result<DefaultKeyword> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(DefaultKeyword {
+            show_keyword: get_val!(value, "data", "showKeyword")?,
+            real_keyword: get_val!(value, "data", "realkeyword")?,
+        });
+    }
+    Err(anyhow!("get default keyword err!"))
+}
+
+/// 轮播信息
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BannersInfo {
+    /// 轮播图
+    pub pic: String,
+    /// 内容 id
+    pub target_id: u64,
+    /// 内容类型
+    pub target_type: TargetType,
+    /// 类型标题 (如 "新歌首发")
+    #[serde(default)]
+    pub type_title: String,
+    /// 链接地址
+    #[serde(default)]
+    pub url: String,
+    /// 加密 id (视频等)
+    #[serde(default)]
+    pub encode_id: String,
+}
+
+#[allow(unused)]
+pub fn to_banners_info(json: String) -> Result<Vec<BannersInfo>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "banners")?;
+        return array.iter().map(to_banner).collect();
+    }
+    Err(anyhow!("none"))
+}
+
+/// 网页版为 imageUrl，客户端为 pic
+fn to_banner(v: &Value) -> Result<BannersInfo> {
+    let url: String = get_val!(v, "url").unwrap_or_default();
+    let encode_id: String = get_val!(v, "encodeId").unwrap_or_default();
+    let target_type = match TargetType::from(get_val!(@as i32, v, "targetType")?) {
+        TargetType::Url(_) => TargetType::Url(url.clone()),
+        TargetType::Video(_) => TargetType::Video(encode_id.clone()),
+        t => t,
+    };
+    Ok(BannersInfo {
+        pic: get_val!(v, "imageUrl").or_else(|_| get_val!(v, "pic"))?,
+        target_id: get_val!(v, "targetId").unwrap_or_default(),
+        target_type,
+        type_title: get_val!(v, "typeTitle").unwrap_or_default(),
+        url,
+        encode_id,
+    })
+}
+
+/// 首页资源 (歌单、歌曲、专辑、电台等)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HomepageResource {
+    /// 资源 id
+    pub id: String,
+    /// 资源类型 (list/song/album/voiceList 等)
+    pub resource_type: String,
+    /// 标题
+    pub title: String,
+    /// 副标题 (歌手、推荐理由等)
+    pub subtitle: String,
+    /// 图片
+    pub image_url: String,
+}
+
+fn to_homepage_resource(v: &Value) -> Result<HomepageResource> {
+    let subtitle = get_val!(v, "uiElement", "subTitle", "title").or_else(|_| {
+        get_val!(@as &Vec<Value>, v, "resourceExtInfo", "artists")
+            .map(|a| {
+                a.iter()
+                    .filter_map(|a| get_val!(@as String, a, "name").ok())
+            })
+            .map(|names| names.collect::<Vec<_>>().join("/"))
+    });
+    Ok(HomepageResource {
+        id: id_string(get_val!(v, "resourceId")?)?,
+        resource_type: get_val!(v, "resourceType").unwrap_or_default(),
+        title: get_val!(v, "uiElement", "mainTitle", "title")?,
+        subtitle: subtitle.unwrap_or_default(),
+        image_url: get_val!(v, "uiElement", "image", "imageUrl").unwrap_or_default(),
+    })
+}
+
+/// 首页排行榜
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HomepageToplist {
+    /// 排行榜 id
+    pub id: String,
+    /// 排行榜名称
+    pub name: String,
+    /// 榜单歌曲
+    pub songs: Vec<HomepageResource>,
+}
+
+/// 首页区块
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum HomepageBlock {
+    /// 轮播图
+    Banner(Vec<BannersInfo>),
+    /// 推荐歌单
+    Playlists {
+        title: String,
+        items: Vec<HomepageResource>,
+    },
+    /// 风格推荐
+    StyleRecommend {
+        title: String,
+        items: Vec<HomepageResource>,
+    },
+    /// 新歌/新碟
+    NewSongsAlbums {
+        title: String,
+        songs: Vec<HomepageResource>,
+        albums: Vec<HomepageResource>,
+    },
+    /// 电台/播客
+    Radio {
+        title: String,
+        items: Vec<HomepageResource>,
+    },
+    /// 排行榜
+    Toplist {
+        title: String,
+        lists: Vec<HomepageToplist>,
+    },
+    /// 未支持的区块
+    Other { code: String, show_type: String },
+}
+
+/// 首页
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HomepagePage {
+    /// 区块列表
+    pub blocks: Vec<HomepageBlock>,
+    /// 翻页游标
+    pub cursor: Option<String>,
+    /// 是否还有更多
+    pub has_more: bool,
+}
+
+fn to_homepage_block(v: &Value) -> Result<HomepageBlock> {
+    let code: String = get_val!(v, "blockCode")?;
+    let title: String = get_val!(v, "uiElement", "subTitle", "title")
+        .or_else(|_| get_val!(v, "uiElement", "mainTitle", "title"))
+        .unwrap_or_default();
+    let list = vec![];
+    let creatives: &Vec<Value> = get_val!(v, "creatives").unwrap_or(&list);
+    // 单个条目解析失败时跳过，不影响整个区块
+    let resources = |c: &Value| -> Vec<HomepageResource> {
+        get_val!(@as &Vec<Value>, c, "resources")
+            .unwrap_or(&list)
+            .iter()
+            .filter_map(|r| to_homepage_resource(r).ok())
+            .collect()
+    };
+    let items = || -> Vec<HomepageResource> { creatives.iter().flat_map(resources).collect() };
+    Ok(match code.as_str() {
+        "HOMEPAGE_BANNER" => {
+            let array: &Vec<Value> = get_val!(v, "extInfo", "banners")?;
+            HomepageBlock::Banner(array.iter().filter_map(|b| to_banner(b).ok()).collect())
+        }
+        "HOMEPAGE_BLOCK_PLAYLIST_RCMD"
+        | "HOMEPAGE_BLOCK_MGC_PLAYLIST"
+        | "HOMEPAGE_BLOCK_OFFICIAL_PLAYLIST" => HomepageBlock::Playlists {
+            title,
+            items: items(),
+        },
+        "HOMEPAGE_BLOCK_STYLE_RCMD" => HomepageBlock::StyleRecommend {
+            title,
+            items: items(),
+        },
+        "HOMEPAGE_BLOCK_NEW_ALBUM_NEW_SONG" => {
+            let (mut songs, mut albums) = (Vec::new(), Vec::new());
+            for item in items() {
+                if item.resource_type == "song" {
+                    songs.push(item);
+                } else {
+                    albums.push(item);
+                }
+            }
+            HomepageBlock::NewSongsAlbums {
+                title,
+                songs,
+                albums,
+            }
+        }
+        "HOMEPAGE_BLOCK_TOPLIST" => HomepageBlock::Toplist {
+            title,
+            lists: creatives
+                .iter()
+                .filter_map(|c| {
+                    Some(HomepageToplist {
+                        id: id_string(get_val!(c, "creativeId").ok()?).ok()?,
+                        name: get_val!(c, "uiElement", "mainTitle", "title").unwrap_or_default(),
+                        songs: resources(c),
+                    })
+                })
+                .collect(),
+        },
+        code if code.contains("VOICELIST") || code.contains("PODCAST") => HomepageBlock::Radio {
+            title,
+            items: items(),
+        },
+        _ => HomepageBlock::Other {
+            show_type: get_val!(v, "showType").unwrap_or_default(),
+            code,
+        },
+    })
+}
+
+#[allow(unused)]
+pub fn to_homepage(json: String) -> Result<HomepagePage> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let data: &Value = get_val!(value, "data")?;
+        let array: &Vec<Value> = get_val!(data, "blocks")?;
+        return Ok(HomepagePage {
+            blocks: array
+                .iter()
+                .filter_map(|b| to_homepage_block(b).ok())
+                .collect(),
+            cursor: get_val!(data, "cursor").ok(),
+            has_more: get_val!(data, "hasMore").unwrap_or_default(),
+        });
+    }
+    Err(anyhow!("get homepage err!"))
+}
+
+#[allow(unused)]
+pub fn to_captcha(json: String) -> Result<()> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code.eq(&200) {
+        return Ok(());
+    }
+    let data: bool = get_val!(value, "data")?;
+    if data {
+        return Ok(());
+    }
+    Err(anyhow!("get captcha err!"))
+}
+
+#[allow(unused)]
+pub fn to_unikey(json: String) -> Result<String> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+
+    if code.eq(&200) {
+        let unikey: String = get_val!(value, "unikey")?;
+        return Ok(unikey);
+    }
+    Err(anyhow!("get unikey err!"))
+}
+
+/// 轮播信息
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TopList {
     /// 榜单 id
     pub id: u64,
     /// 榜单名
     pub name: String,
-    /// 更新时间
+    /// 更新时间
+    pub update: String,
+    /// 榜单简介
+    pub description: String,
+    /// 榜单封面
+    pub cover: String,
+}
+
+#[allow(unused)]
+pub fn to_toplist(json: String) -> Result<Vec<TopList>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+
+    if code.eq(&200) {
+        let list: &Vec<Value> = get_val!(value, "list")?;
+        let mut toplist = Vec::new();
+        for t in list {
+            toplist.push(TopList {
+                id: get_val!(t, "id")?,
+                name: get_val!(t, "name")?,
+                update: get_val!(t, "updateFrequency").unwrap_or_default(),
+                description: get_val!(t, "description").unwrap_or_default(),
+                cover: get_val!(t, "coverImgUrl").unwrap_or_default(),
+            });
+        }
+        return Ok(toplist);
+    }
+    Err(anyhow!("get toplist err!"))
+}
+
+/// 歌单排序方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PlaylistOrder {
+    /// 热门
+    #[default]
+    Hot,
+    /// 最新
+    New,
+}
+
+impl PlaylistOrder {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hot => "hot",
+            Self::New => "new",
+        }
+    }
+}
+
+/// 歌单分类大类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CatalogueKind {
+    /// 语种
+    Language,
+    /// 风格
+    Style,
+    /// 场景
+    Scene,
+    /// 情感
+    Emotion,
+    /// 主题
+    Theme,
+    Other(u32),
+}
+
+impl From<u32> for CatalogueKind {
+    fn from(c: u32) -> Self {
+        match c {
+            0 => Self::Language,
+            1 => Self::Style,
+            2 => Self::Scene,
+            3 => Self::Emotion,
+            4 => Self::Theme,
+            _ => Self::Other(c),
+        }
+    }
+}
+
+/// 歌单标签
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlaylistTag {
+    /// 标签 id (歌单分类中为 0)
+    pub id: u64,
+    /// 标签名，即 top_song_list 的 cat 参数
+    pub name: String,
+    /// 所属大类
+    pub category: u32,
+    /// 是否热门
+    pub hot: bool,
+}
+
+/// 歌单分类组
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CatalogueGroup {
+    /// 大类
+    pub kind: CatalogueKind,
+    /// 大类名称
+    pub name: String,
+    /// 分类
+    pub tags: Vec<PlaylistTag>,
+}
+
+/// 歌单分类
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlaylistCatalogue {
+    /// "全部" 分类名
+    pub all: String,
+    /// 按大类分组，按大类代码排序
+    pub groups: Vec<CatalogueGroup>,
+}
+
+fn to_playlist_tag(v: &Value) -> Result<PlaylistTag> {
+    Ok(PlaylistTag {
+        id: get_val!(v, "id").unwrap_or_default(),
+        name: get_val!(v, "name")?,
+        category: get_val!(v, "category").unwrap_or_default(),
+        hot: get_val!(v, "hot").unwrap_or_default(),
+    })
+}
+
+#[allow(unused)]
+pub fn to_playlist_catalogue(json: String) -> Result<PlaylistCatalogue> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let categories: &Value = get_val!(value, "categories")?;
+        let mut groups: Vec<CatalogueGroup> = categories
+            .as_object()
+            .ok_or_else(|| anyhow!("json not a object"))?
+            .iter()
+            .filter_map(|(k, name)| {
+                Some(CatalogueGroup {
+                    kind: CatalogueKind::from(k.parse::<u32>().ok()?),
+                    name: name.as_str()?.to_owned(),
+                    tags: vec![],
+                })
+            })
+            .collect();
+        groups.sort_by_key(|g| match g.kind {
+            CatalogueKind::Language => 0,
+            CatalogueKind::Style => 1,
+            CatalogueKind::Scene => 2,
+            CatalogueKind::Emotion => 3,
+            CatalogueKind::Theme => 4,
+            CatalogueKind::Other(c) => c,
+        });
+        let array: &Vec<Value> = get_val!(value, "sub")?;
+        for v in array.iter() {
+            let tag = to_playlist_tag(v)?;
+            let kind = CatalogueKind::from(tag.category);
+            if let Some(group) = groups.iter_mut().find(|g| g.kind == kind) {
+                group.tags.push(tag);
+            }
+        }
+        return Ok(PlaylistCatalogue {
+            all: get_val!(value, "all", "name").unwrap_or_default(),
+            groups,
+        });
+    }
+    Err(anyhow!("get playlist catalogue err!"))
+}
+
+#[allow(unused)]
+pub fn to_playlist_hot_tags(json: String) -> Result<Vec<PlaylistTag>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "tags")?;
+        return array
+            .iter()
+            .map(|v| {
+                let mut tag = to_playlist_tag(v.get("playlistTag").unwrap_or(v))?;
+                tag.hot = true;
+                Ok(tag)
+            })
+            .collect();
+    }
+    Err(anyhow!("get playlist hot tags err!"))
+}
+
+/// 排行榜分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ToplistCategory {
+    /// 官方榜
+    Official,
+    /// 全球榜
+    Global,
+    /// 曲风/特色榜
+    Genre,
+}
+
+/// 全球榜 id
+/// 60198: 美国Billboard榜
+/// 180106: UK排行榜周榜
+/// 60131: 日本Oricon榜
+/// 3812895: Beatport全球电子舞曲榜
+/// 27135204: 法国 NRJ Vos Hits 周榜
+/// 11641012: iTunes榜
+/// 120001: Hit FM Top榜
+const GLOBAL_TOPLIST_IDS: [u64; 7] = [60198, 180106, 60131, 3812895, 27135204, 11641012, 120001];
+
+/// 排行榜详情
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ToplistDetail {
+    /// 榜单 id
+    pub id: u64,
+    /// 榜单名
+    pub name: String,
+    /// 更新频率
     pub update: String,
     /// 榜单简介
     pub description: String,
     /// 榜单封面
     pub cover: String,
+    /// 分类
+    pub category: ToplistCategory,
+    /// 前三名 (歌名, 歌手)
+    pub preview: Vec<(String, String)>,
+    /// 播放次数
+    pub play_count: u64,
+    /// 更新时间(毫秒)
+    pub update_time: u64,
 }
 
 #[allow(unused)]
-pub fn to_toplist(json: String) -> Result<Vec<TopList>> {
+pub fn to_toplist_detail(json: String) -> Result<Vec<ToplistDetail>> {
     let value = &serde_json::from_str::<Value>(&json)?;
     let code: i32 = get_val!(value, "code")?;
-
-    if code.eq(&200) {
+    if code == 200 {
         let list: &Vec<Value> = get_val!(value, "list")?;
-        let mut toplist = Vec::new();
-        for t in list {
-            toplist.push(TopList {
-                id: get_val!(t, "id")?,
-                name: get_val!(t, "name")?,
-                update: get_val!(t, "updateFrequency").unwrap_or_default(),
-                description: get_val!(t, "description").unwrap_or_default(),
-                cover: get_val!(t, "coverImgUrl").unwrap_or_default(),
-            });
+        return list
+            .iter()
+            .map(|t| {
+                let id: u64 = get_val!(t, "id")?;
+                let toplist_type: String = get_val!(t, "ToplistType").unwrap_or_default();
+                let category = if !toplist_type.is_empty() {
+                    ToplistCategory::Official
+                } else if GLOBAL_TOPLIST_IDS.contains(&id) {
+                    ToplistCategory::Global
+                } else {
+                    ToplistCategory::Genre
+                };
+                let preview = get_val!(@as &Vec<Value>, t, "tracks")
+                    .map(|tracks| {
+                        tracks
+                            .iter()
+                            .filter_map(|v| {
+                                Some((get_val!(v, "first").ok()?, get_val!(v, "second").ok()?))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Ok(ToplistDetail {
+                    id,
+                    name: get_val!(t, "name")?,
+                    update: get_val!(t, "updateFrequency").unwrap_or_default(),
+                    description: get_val!(t, "description").unwrap_or_default(),
+                    cover: get_val!(t, "coverImgUrl").unwrap_or_default(),
+                    category,
+                    preview,
+                    play_count: get_val!(t, "playCount").unwrap_or_default(),
+                    update_time: get_val!(t, "updateTime").unwrap_or_default(),
+                })
+            })
+            .collect();
+    }
+    Err(anyhow!("get toplist detail err!"))
+}
+
+/// 排名变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RankChange {
+    /// 新上榜
+    New,
+    /// 上升
+    Up(u32),
+    /// 下降
+    Down(u32),
+    /// 不变
+    Same,
+}
+
+/// 排行榜歌曲
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RankedSong {
+    /// 当前排名 (从 1 开始)
+    pub rank: u32,
+    /// 上期排名，新上榜为 None
+    pub last_rank: Option<u32>,
+    /// 歌曲信息
+    pub song: SongInfo,
+}
+
+impl RankedSong {
+    /// 排名变化
+    pub fn change(&self) -> RankChange {
+        match self.last_rank {
+            None => RankChange::New,
+            Some(last) if last > self.rank => RankChange::Up(last - self.rank),
+            Some(last) if last < self.rank => RankChange::Down(self.rank - last),
+            Some(_) => RankChange::Same,
         }
-        return Ok(toplist);
     }
-    Err(anyhow!("get toplist err!"))
+}
+
+/// 解析排行榜歌单详情，上期排名取自 trackIds 的 lr (从 0 开始)
+#[allow(unused)]
+pub fn to_ranked_songs(json: &Value) -> Result<Vec<RankedSong>> {
+    let detail = to_mix_detail(json)?;
+    let list = vec![];
+    let track_ids: &Vec<Value> = get_val!(json, "playlist", "trackIds").unwrap_or(&list);
+    Ok(detail
+        .songs
+        .into_iter()
+        .enumerate()
+        .map(|(i, song)| {
+            let last_rank = track_ids
+                .iter()
+                .find(|t| get_val!(@as u64, t, "id").is_ok_and(|id| id == song.id))
+                .and_then(|t| get_val!(@as u32, t, "lr").ok())
+                .map(|lr| lr + 1);
+            RankedSong {
+                rank: i as u32 + 1,
+                last_rank,
+                song,
+            }
+        })
+        .collect())
 }
 
 /// 歌单详情动态
@@ -1042,6 +3321,297 @@ pub fn to_album_detail_dynamic(json: String) -> Result<AlbumDetailDynamic> {
     Err(anyhow!("get album detail dynamic err!"))
 }
 
+/// 云盘信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CloudDisk {
+    /// 已用空间(字节)
+    pub size: u64,
+    /// 总空间(字节)
+    pub max_size: u64,
+    /// 歌曲总数
+    pub count: u64,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 当前页歌曲
+    pub songs: Vec<CloudSong>,
+}
+
+/// 云盘歌曲
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CloudSong {
+    /// 云盘歌曲 id
+    pub song_id: u64,
+    /// 歌名
+    pub song_name: String,
+    /// 歌手
+    pub artist: String,
+    /// 专辑
+    pub album: String,
+    /// 文件名
+    pub file_name: String,
+    /// 码率
+    pub bitrate: u32,
+    /// 文件大小(字节)
+    pub file_size: u64,
+    /// 上传时间
+    pub add_time: u64,
+    /// 歌曲时长
+    pub duration: u64,
+    /// 匹配的曲库歌曲 id，未匹配时为 0
+    pub matched_id: u64,
+}
+
+/// 数字或字符串形式的数值
+fn u64_lossy(v: &Value) -> Result<u64> {
+    match v {
+        Value::String(s) => Ok(s.parse()?),
+        _ => u64::dval(v),
+    }
+}
+
+#[allow(unused)]
+pub fn to_cloud_disk(json: String) -> Result<CloudDisk> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "data")?;
+        let mut songs = Vec::new();
+        for v in array.iter() {
+            songs.push(CloudSong {
+                song_id: get_val!(v, "songId")?,
+                song_name: get_val!(v, "songName")?,
+                artist: get_val!(v, "artist").unwrap_or_default(),
+                album: get_val!(v, "album").unwrap_or_default(),
+                file_name: get_val!(v, "fileName").unwrap_or_default(),
+                bitrate: get_val!(v, "bitrate").unwrap_or_default(),
+                file_size: get_val!(v, "fileSize").unwrap_or_default(),
+                add_time: get_val!(v, "addTime").unwrap_or_default(),
+                duration: get_val!(v, "simpleSong", "dt").unwrap_or_default(),
+                matched_id: get_val!(@as &Value, v, "matchSongId")
+                    .and_then(u64_lossy)
+                    .unwrap_or_default(),
+            });
+        }
+        return Ok(CloudDisk {
+            size: u64_lossy(get_val!(value, "size")?)?,
+            max_size: u64_lossy(get_val!(value, "maxSize")?)?,
+            count: get_val!(value, "count")?,
+            has_more: get_val!(value, "hasMore").unwrap_or_default(),
+            songs,
+        });
+    }
+    Err(anyhow!("get cloud disk err!"))
+}
+
+/// 云盘上传进度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadProgress {
+    /// 计算文件 md5
+    Hashing,
+    /// 检查云盘是否已有该文件
+    Checking,
+    /// 上传文件 (已上传字节数, 总字节数)
+    Uploading(u64, u64),
+    /// 提交歌曲信息
+    Submitting,
+    /// 发布到云盘
+    Publishing,
+}
+
+/// 云盘上传检查结果
+pub(crate) struct CloudUploadCheck {
+    pub song_id: String,
+    pub need_upload: bool,
+}
+
+/// NOS 上传凭证
+pub(crate) struct NosToken {
+    pub bucket: String,
+    pub object_key: String,
+    pub token: String,
+    pub resource_id: String,
+}
+
+/// 数字或字符串形式的 id，字符串 id 可能不是数字 (如视频 id)
+fn id_string(v: &Value) -> Result<String> {
+    match v.as_str() {
+        Some(s) => Ok(s.to_owned()),
+        None => Ok(u64_lossy(v)?.to_string()),
+    }
+}
+
+#[allow(unused)]
+pub(crate) fn to_cloud_upload_check(json: String) -> Result<CloudUploadCheck> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(CloudUploadCheck {
+            song_id: u64_lossy(get_val!(value, "songId")?)?.to_string(),
+            need_upload: get_val!(value, "needUpload")?,
+        });
+    }
+    Err(anyhow!("cloud upload check err: {}", code))
+}
+
+#[allow(unused)]
+pub(crate) fn to_nos_token(json: String) -> Result<NosToken> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(NosToken {
+            bucket: get_val!(value, "result", "bucket")
+                .unwrap_or_else(|_| "jd-musicrep-privatecloud-audio-public".to_owned()),
+            object_key: get_val!(value, "result", "objectKey")?,
+            token: get_val!(value, "result", "token")?,
+            resource_id: u64_lossy(get_val!(value, "result", "resourceId")?)?.to_string(),
+        });
+    }
+    Err(anyhow!("alloc nos token err: {}", code))
+}
+
+/// 返回云盘歌曲 id
+#[allow(unused)]
+pub(crate) fn to_cloud_song_id(json: String) -> Result<String> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(u64_lossy(get_val!(value, "songId")?)?.to_string());
+    }
+    Err(anyhow!("cloud upload info err: {}", code))
+}
+
+/// 心动模式歌曲
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IntelligenceTrack {
+    /// 歌曲信息
+    pub song: SongInfo,
+    /// true 为推荐歌曲，false 为歌单内歌曲
+    pub recommended: bool,
+    /// 推荐算法
+    pub alg: String,
+}
+
+#[allow(unused)]
+pub fn to_intelligence_tracks(json: String) -> Result<Vec<IntelligenceTrack>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let array: &Vec<Value> = get_val!(value, "data")?;
+    let songs = to_song_info(json, Parse::Intelligence)?;
+    Ok(array
+        .iter()
+        .zip(songs)
+        .map(|(v, song)| IntelligenceTrack {
+            song,
+            recommended: get_val!(v, "recommended").unwrap_or_default(),
+            alg: get_val!(v, "alg").unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// 私人 FM 模式
+/// Default: 默认
+/// Familiar: 熟悉
+/// Explore: 探索
+/// Exercise: 场景-运动
+/// Focus: 场景-专注
+/// NightEmo: 场景-夜晚
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FmMode {
+    #[default]
+    Default,
+    Familiar,
+    Explore,
+    Exercise,
+    Focus,
+    NightEmo,
+}
+
+impl FmMode {
+    /// 接口使用的 mode 及 subMode
+    pub fn as_params(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Self::Default => ("DEFAULT", None),
+            Self::Familiar => ("FAMILIAR", None),
+            Self::Explore => ("EXPLORE", None),
+            Self::Exercise => ("SCENE_RCMD", Some("EXERCISE")),
+            Self::Focus => ("SCENE_RCMD", Some("FOCUS")),
+            Self::NightEmo => ("SCENE_RCMD", Some("NIGHT_EMO")),
+        }
+    }
+}
+
+/// 播放结束方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PlayEnd {
+    /// 播放完成
+    PlayEnd,
+    /// 用户切歌
+    Ui,
+    /// 播放中断
+    Interrupt,
+}
+
+impl PlayEnd {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PlayEnd => "playend",
+            Self::Ui => "ui",
+            Self::Interrupt => "interrupt",
+        }
+    }
+}
+
+/// 播放事件
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PlayEvent {
+    /// 开始播放
+    Start { song_id: u64, source_id: u64 },
+    /// 结束播放
+    End {
+        song_id: u64,
+        source_id: u64,
+        /// 已播放时长(秒)
+        played_seconds: u64,
+        end: PlayEnd,
+    },
+}
+
+impl PlayEvent {
+    /// 转换为 weblog 日志
+    pub fn to_log(&self) -> Value {
+        match self {
+            Self::Start { song_id, .. } => serde_json::json!({
+                "action": "startplay",
+                "json": {
+                    "id": song_id.to_string(),
+                    "type": "song",
+                    "content": format!("id={}", song_id),
+                    "mainsite": 1,
+                },
+            }),
+            Self::End {
+                song_id,
+                source_id,
+                played_seconds,
+                end,
+            } => serde_json::json!({
+                "action": "play",
+                "json": {
+                    "download": 0,
+                    "end": end.as_str(),
+                    "id": song_id.to_string(),
+                    "sourceId": source_id.to_string(),
+                    "time": played_seconds,
+                    "type": "song",
+                    "wifi": 0,
+                    "source": "list",
+                    "mainsite": 1,
+                    "content": "",
+                },
+            }),
+        }
+    }
+}
+
 /// 请求方式
 #[allow(unused)]
 #[derive(Debug)]
@@ -1063,6 +3633,11 @@ pub enum Method {
 /// Singer: 歌手热门单曲
 /// Dj: 电台
 /// Intelligence: 心动/智能
+/// Follows: 关注
+/// Followeds: 粉丝
+/// Subscribers: 歌单收藏者
+/// AlbumProduct: 数字专辑
+/// PaidAlbum: 已购数字专辑
 #[allow(unused)]
 #[derive(Debug, Clone)]
 pub enum Parse {
@@ -1080,6 +3655,11 @@ pub enum Parse {
     SingerSongs,
     Radio,
     Intelligence,
+    Follows,
+    Followeds,
+    Subscribers,
+    AlbumProduct,
+    PaidAlbum,
 }
 
 /// 客户端类型
@@ -1093,12 +3673,24 @@ pub enum ClientType {
 }
 
 /// 轮播内容类型
+/// Video: 视频 id
+/// Url: 链接地址
+/// Unknown: 未知类型代码
 #[allow(unused)]
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub enum TargetType {
     Song,
     Album,
-    Unknown,
+    Singer,
+    SongList,
+    User,
+    Mv,
+    Radio,
+    /// 电台节目
+    Program,
+    Video(String),
+    Url(String),
+    Unknown(i32),
 }
 
 impl From<i32> for TargetType {
@@ -1106,7 +3698,25 @@ impl From<i32> for TargetType {
         match t {
             1 => Self::Song,
             10 => Self::Album,
-            _ => Self::Unknown,
+            100 => Self::Singer,
+            1000 => Self::SongList,
+            1002 => Self::User,
+            1004 => Self::Mv,
+            1001 => Self::Program,
+            1009 => Self::Radio,
+            1014 => Self::Video(String::new()),
+            3000 => Self::Url(String::new()),
+            _ => Self::Unknown(t),
+        }
+    }
+}
+
+impl ClientType {
+    /// 请求使用的 User-Agent 类别
+    pub(crate) fn user_agent(&self) -> &'static str {
+        match self {
+            Self::Pc => "pc",
+            _ => "mobile",
         }
     }
 }
@@ -1127,6 +3737,490 @@ impl fmt::Display for ClientType {
 mod tests {
     use super::*;
 
+    #[test]
+    fn song_url_reports_unavailable_reason() {
+        let json = r#"{"code":200,"data":[
+            {"id":1,"url":"http://a.mp3","br":320000,"size":8000000,"md5":"0cc175b9c0f1b6a831c399e269772661","type":"mp3","encodeType":"mp3","expi":1200,"level":"exhigh","code":200,"fee":8,"freeTrialInfo":null},
+            {"id":2,"url":"http://b.mp3","br":128000,"level":"standard","code":200,"fee":1,"freeTrialInfo":{"start":0,"end":30}},
+            {"id":3,"url":null,"br":0,"code":404,"fee":0,"freeTrialInfo":null},
+            {"id":4,"url":null,"br":0,"code":-110,"fee":4,"freeTrialInfo":null}
+        ]}"#;
+        let urls = to_song_url(json.to_string()).unwrap();
+        assert_eq!(urls.len(), 4);
+        assert_eq!(urls[0].quality, SongQuality::Extreme);
+        assert_eq!(urls[0].file_type, "mp3");
+        assert_eq!(urls[0].expi, 1200);
+        assert!(urls[0].playable());
+        assert!(urls[0].check_md5(b"a"));
+        assert!(urls[1].is_trial());
+        assert_eq!(urls[1].free_trial.as_ref().unwrap().end, 30);
+        assert_eq!(urls[1].unavailable, Some(SongUnavailable::TrialOnly));
+        assert!(urls[2].url.is_empty());
+        assert_eq!(urls[2].unavailable, Some(SongUnavailable::NoCopyright));
+        assert_eq!(urls[3].unavailable, Some(SongUnavailable::PaymentRequired));
+        assert_eq!(
+            SongQuality::ladder(SongQuality::Lossless, SongQuality::Higher).unwrap(),
+            vec![
+                SongQuality::Lossless,
+                SongQuality::Extreme,
+                SongQuality::Higher
+            ]
+        );
+        assert_eq!(
+            SongQuality::ladder(SongQuality::Master, SongQuality::Lossless).unwrap(),
+            vec![
+                SongQuality::Master,
+                SongQuality::HiRes,
+                SongQuality::Lossless
+            ]
+        );
+        assert_eq!(
+            SongQuality::ladder(SongQuality::Surround, SongQuality::HiRes).unwrap(),
+            vec![SongQuality::Surround, SongQuality::HiRes]
+        );
+        assert!(SongQuality::ladder(SongQuality::Higher, SongQuality::Lossless).is_err());
+        assert!(SongQuality::Master.is_stereo());
+        assert!(!SongQuality::Surround.is_stereo());
+    }
+
+    #[test]
+    fn to_song_info_attaches_privileges() {
+        let json = r#"{"code":200,"songs":[
+            {"id":1,"name":"a","ar":[{"name":"x"}],"al":{"id":10,"name":"al","picUrl":""},"dt":1000},
+            {"id":2,"name":"b","ar":[{"name":"y"}],"al":{"id":10,"name":"al","picUrl":""},"dt":1000}
+        ],"privileges":[
+            {"id":2,"fee":0,"st":-200,"pl":0,"dl":0,"maxbr":999000,"maxBrLevel":"lossless","plLevel":"none","dlLevel":"none","sp":0,"cp":0,"subp":0,"chargeInfoList":null},
+            {"id":1,"fee":8,"st":0,"pl":320000,"dl":0,"maxbr":999000,"maxBrLevel":"hires","plLevel":"exhigh","dlLevel":"none","sp":7,"cp":1,"subp":1,
+             "chargeInfoList":[{"rate":128000,"chargeType":0},{"rate":999000,"chargeType":1}]}
+        ]}"#;
+        let songs = to_song_info(json.to_string(), Parse::Usl).unwrap();
+        let p = songs[0].privilege.as_ref().unwrap();
+        assert!(p.playable());
+        assert!(!p.downloadable());
+        assert_eq!(p.max_level, Some(SongQuality::HiRes));
+        assert_eq!(p.play_level, Some(SongQuality::Extreme));
+        assert_eq!(p.download_level, None);
+        assert_eq!(p.charge_info.len(), 2);
+        assert_eq!(songs[0].copyright, SongCopyright::VipOnlyHighRate);
+        assert!(!songs[1].privilege.as_ref().unwrap().playable());
+        assert_eq!(songs[1].copyright, SongCopyright::Unavailable);
+
+        let free_no_rate: Value =
+            serde_json::from_str(r#"{"id":3,"fee":0,"st":0,"pl":0}"#).unwrap();
+        assert_eq!(
+            SongCopyright::from_privilege(&free_no_rate).unwrap(),
+            Privilege::from_value(&free_no_rate).unwrap().copyright()
+        );
+    }
+
+    #[test]
+    fn to_cloud_disk_parses_quota() {
+        let json = r#"{"code":200,"size":"2147483648","maxSize":"64424509440","upgradeSign":0,"count":1,"hasMore":false,"data":[
+            {"songId":1901371647,"songName":"稀有录音","artist":"未知艺术家","album":"未知专辑","fileName":"rare.flac",
+             "bitrate":999,"fileSize":31457280,"addTime":1700000000000,"matchSongId":"186016","simpleSong":{"id":1901371647,"dt":240000}}
+        ]}"#;
+        let disk = to_cloud_disk(json.to_string()).unwrap();
+        assert_eq!(disk.size, 2147483648);
+        assert_eq!(disk.max_size, 64424509440);
+        assert_eq!(disk.count, 1);
+        assert!(!disk.has_more);
+        assert_eq!(disk.songs[0].file_name, "rare.flac");
+        assert_eq!(disk.songs[0].duration, 240000);
+        assert_eq!(disk.songs[0].matched_id, 186016);
+    }
+
+    #[test]
+    fn to_cloud_upload_parses_steps() {
+        let check = to_cloud_upload_check(
+            r#"{"code":200,"songId":"1901371647","needUpload":true}"#.to_owned(),
+        )
+        .unwrap();
+        assert_eq!(check.song_id, "1901371647");
+        assert!(check.need_upload);
+        assert!(to_cloud_upload_check(r#"{"code":400}"#.to_owned()).is_err());
+
+        let token = to_nos_token(
+            r#"{"code":200,"result":{"objectKey":"obj/a.flac","token":"UPLOAD x","resourceId":1001}}"#
+                .to_owned(),
+        )
+        .unwrap();
+        assert_eq!(token.bucket, "jd-musicrep-privatecloud-audio-public");
+        assert_eq!(token.object_key, "obj/a.flac");
+        assert_eq!(token.resource_id, "1001");
+
+        let id = to_cloud_song_id(r#"{"code":200,"songId":1901371647}"#.to_owned()).unwrap();
+        assert_eq!(id, "1901371647");
+    }
+
+    #[test]
+    fn to_play_record_parses_week_data() {
+        let json = r#"{"code":200,"weekData":[
+            {"playCount":42,"score":100,"song":{"id":1,"name":"a","ar":[{"name":"x"}],"al":{"id":10,"name":"al","picUrl":"p"},"dt":1000}},
+            {"playCount":7,"score":17,"song":{"id":2,"name":"b","ar":[],"al":{"id":11,"name":"bl"},"dt":2000}}
+        ]}"#;
+        let records = to_play_record(json.to_string(), PlayRecordRange::Week).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].play_count, 42);
+        assert_eq!(records[0].song.singer, "x");
+        assert_eq!(records[1].score, 17);
+        assert_eq!(records[1].song.singer, "unknown");
+        assert!(to_play_record(json.to_string(), PlayRecordRange::All).is_err());
+    }
+
+    #[test]
+    fn to_user_detail_parses_profile() {
+        let json = r#"{"code":200,"level":9,"listenSongs":12345,"profile":{
+            "userId":32953014,"nickname":"n","avatarUrl":"http://a","signature":"s","gender":1,
+            "province":110000,"city":110101,"vipType":11,"follows":5,"followeds":8,
+            "playlistCount":20,"playlistBeSubscribedCount":3,"followed":false}}"#;
+        let profile = to_user_detail(json.to_string()).unwrap();
+        assert_eq!(profile.uid, 32953014);
+        assert_eq!(profile.level, 9);
+        assert_eq!(profile.listen_songs, 12345);
+        assert_eq!(profile.followeds, 8);
+        assert_eq!(profile.playlist_count, 20);
+
+        let json = r#"{"code":200,"more":false,"follow":[{"userId":1,"nickname":"a"}]}"#;
+        let follows = to_user_list(json.to_string(), Parse::Follows).unwrap();
+        assert_eq!(follows.users[0].nickname, "a");
+        assert_eq!(follows.users[0].signature, "");
+        assert!(!follows.more);
+
+        let json = r#"{"code":200,"more":true,"followeds":[
+            {"userId":1,"nickname":"a","time":1700000000000},
+            {"userId":2,"nickname":"b","time":1600000000000}]}"#;
+        let followeds = to_user_list(json.to_string(), Parse::Followeds).unwrap();
+        assert!(followeds.more);
+        assert_eq!(followeds.lasttime, 1600000000000);
+        assert!(to_user_list(json.to_string(), Parse::Top).is_err());
+    }
+
+    #[test]
+    fn to_vip_status_gates_quality() {
+        let json = r#"{"code":200,"data":{"redVipLevel":7,
+            "musicPackage":{"vipCode":0,"expireTime":0},
+            "associator":{"vipCode":100,"expireTime":4102444800000},
+            "redplus":{"vipCode":300,"expireTime":1600000000000}}}"#;
+        let vip = to_vip_status(json.to_string()).unwrap();
+        assert!(vip.music_package.is_none());
+        assert!(vip.is_black_vinyl());
+        assert!(!vip.is_svip());
+        assert_eq!(vip.max_quality(), SongQuality::HiRes);
+        assert_eq!(VipStatus::default().max_quality(), SongQuality::Extreme);
+
+        let json = r#"{"code":200,"data":{"userId":1,"level":8,"progress":0.5,
+            "nowPlayCount":5000,"nextPlayCount":10000,"nowLoginCount":500,"nextLoginCount":800}}"#;
+        let level = to_user_level(json.to_string()).unwrap();
+        assert_eq!(level.level, 8);
+        assert_eq!(level.progress, 0.5);
+    }
+
+    #[test]
+    fn to_search_result_parses_totals() {
+        let json = r#"{"code":200,"result":{"mvCount":3,"mvs":[
+            {"id":5436712,"name":"晴天","cover":"http://c","artistName":"周杰伦","duration":269000,"playCount":100},
+            {"id":10929721,"name":"晴天 live","cover":"http://d","artistName":"周杰伦","duration":300000,"playCount":5}]}}"#;
+        let result = to_search_result(json.to_string(), SearchType::Mv, 0).unwrap();
+        assert_eq!(result.total, 3);
+        assert!(result.has_more);
+        match result.items {
+            SearchItems::Mvs(mvs) => assert_eq!(mvs[1].id, 10929721),
+            items => panic!("unexpected items: {:?}", items),
+        }
+
+        let json = r#"{"code":200,"result":{"videoCount":0,"hasMore":false}}"#;
+        let result = to_search_result(json.to_string(), SearchType::Video, 0).unwrap();
+        assert!(!result.has_more);
+        assert!(result.items.is_empty());
+
+        let json = r#"{"code":200,"result":{"order":["artists","songs"],
+            "artists":[{"id":6452,"name":"周杰伦"}],
+            "songs":[{"id":186016,"name":"晴天","artists":[{"id":6452,"name":"周杰伦"}]}]}}"#;
+        let suggest = to_search_suggest(json.to_string()).unwrap();
+        assert_eq!(suggest[0].kind, SearchType::Singer);
+        assert_eq!(suggest[1].subtitle, "周杰伦");
+    }
+
+    #[test]
+    fn to_hot_search_keeps_rank_order() {
+        let json = r#"{"code":200,"data":[
+            {"searchWord":"晴天","score":2310854,"content":"","source":0,"iconType":1,"iconUrl":"http://i","url":"","alg":"alg_hot"},
+            {"searchWord":"稻香","score":1870912,"content":"周杰伦新歌","source":0,"iconType":0,"iconUrl":null,"url":"","alg":"alg_hot"}],
+            "message":"success"}"#;
+        let hot = to_hot_search(json.to_string()).unwrap();
+        assert_eq!(hot.len(), 2);
+        assert_eq!(hot[0].word, "晴天");
+        assert_eq!(hot[0].icon_type, 1);
+        assert_eq!(hot[1].content, "周杰伦新歌");
+
+        let json = r#"{"code":200,"message":null,"data":{"showKeyword":"晴天 周杰伦","realkeyword":"晴天","searchType":1,"action":0,"alg":"alg_search_rec"}}"#;
+        let keyword = to_default_keyword(json.to_string()).unwrap();
+        assert_eq!(keyword.show_keyword, "晴天 周杰伦");
+        assert_eq!(keyword.real_keyword, "晴天");
+    }
+
+    #[test]
+    fn to_lyric_matches_extracts_highlights() {
+        let json = r#"{"code":200,"result":{"songCount":2,"songs":[
+            {"id":186016,"name":"晴天","artists":[{"id":6452,"name":"周杰伦"}],"album":{"id":18905,"name":"叶惠美"},"duration":269000,
+             "lyrics":{"txt":"故事的小黄花\n从出生那年就飘着\n童年的荡秋千","range":[{"first":7,"second":9},{"first":12,"second":14}]}},
+            {"id":185811,"name":"稻香","artists":[{"id":6452,"name":"周杰伦"}],"album":{"id":18896,"name":"魔杰座"},"duration":223000,
+             "lyrics":["还记得你说家是唯一的<b>城堡</b>","随着<b>稻香</b>河流继续奔跑"]}]}}"#;
+        let matches = to_lyric_matches(json.to_string()).unwrap();
+        assert_eq!(matches[0].song.id, 186016);
+        assert_eq!(
+            matches[0].fragments,
+            vec![LyricFragment {
+                text: "从出生那年就飘着".to_owned(),
+                ranges: vec![(0, 2), (5, 7)],
+            }]
+        );
+        assert_eq!(matches[1].fragments[1].text, "随着稻香河流继续奔跑");
+        assert_eq!(matches[1].fragments[1].ranges, vec![(2, 4)]);
+    }
+
+    #[test]
+    fn to_mv_detail_lists_resolutions() {
+        let json = r#"{"loadingPic":"","bufferPic":"","subed":true,"code":200,"data":{
+            "id":5436712,"name":"晴天","artistId":6452,"artistName":"周杰伦","briefDesc":"","desc":"",
+            "cover":"http://p1.music.126.net/c.jpg","playCount":4361432,"duration":269000,"publishTime":"2014-07-25",
+            "brs":[{"size":46117152,"br":1080,"point":0},{"size":9864452,"br":240,"point":0},{"size":17524544,"br":480,"point":0}],
+            "artists":[{"id":6452,"name":"周杰伦"}]}}"#;
+        let mv = to_mv_detail(json.to_string()).unwrap();
+        assert_eq!(mv.artist_name, "周杰伦");
+        assert_eq!(mv.resolutions, vec![240, 480, 1080]);
+        assert!(mv.subscribed);
+
+        let json = r#"{"code":200,"data":{"id":5436712,"url":"http://vodkgeyttp8.vod.126.net/a.mp4?wsSecret=x","r":1080,"size":46117152,"md5":"","code":200,"expi":3600,"fee":0}}"#;
+        let url = to_mv_url(json.to_string()).unwrap();
+        assert_eq!(url.id, "5436712");
+        assert_eq!(url.resolution, 1080);
+
+        let json = r#"{"code":200,"urls":[{"id":"89ADDE33C0AAE8EC14B99F6750DB954D","url":"","size":0,"r":720}]}"#;
+        assert!(to_video_url(json.to_string()).is_err());
+    }
+
+    #[test]
+    fn to_radio_programs_keeps_episode_info() {
+        let json = r#"{"count":120,"more":true,"code":200,"programs":[
+            {"mainSong":{"id":1901371647,"name":"第120期","duration":1800000},
+             "dj":{"userId":1,"nickname":"主播","avatarUrl":"http://a"},
+             "radio":{"id":794062371,"name":"电台"},
+             "id":2495003305,"name":"第120期","description":"节目简介","serialNum":120,
+             "coverUrl":"http://c","duration":1800000,"createTime":1640000000000,
+             "listenerCount":5000,"likedCount":12,"mainTrackId":1901371647}]}"#;
+        let programs = to_radio_programs(json.to_string()).unwrap();
+        assert_eq!(programs[0].serial_num, 120);
+        assert_eq!(programs[0].description, "节目简介");
+        assert_eq!(programs[0].radio_id, 794062371);
+        assert_eq!(programs[0].song.id, 1901371647);
+        assert_eq!(programs[0].song.singer, "第 120 期");
+
+        let json = r#"{"code":200,"data":{"id":794062371,"name":"电台","picUrl":"http://p","desc":"简介",
+            "dj":{"userId":1,"nickname":"主播","avatarUrl":"http://a"},"category":"情感","categoryId":3,
+            "programCount":120,"subCount":3000,"subed":true,"createTime":1600000000000,"lastProgramName":"第120期"}}"#;
+        let radio = to_radio_detail(json.to_string()).unwrap();
+        assert_eq!(radio.dj_name, "主播");
+        assert!(radio.subscribed);
+    }
+
+    #[test]
+    fn to_intelligence_tracks_marks_recommended() {
+        let json = r#"{"code":200,"data":[
+            {"id":186016,"recommended":false,"alg":null,"songInfo":{"id":186016,"name":"晴天","ar":[{"id":6452,"name":"周杰伦"}],"al":{"id":18905,"name":"叶惠美","picUrl":""},"dt":269000}},
+            {"id":185811,"recommended":true,"alg":"hb_rt_sim","songInfo":{"id":185811,"name":"稻香","ar":[{"id":6452,"name":"周杰伦"}],"al":{"id":18896,"name":"魔杰座","picUrl":""},"dt":223000}}]}"#;
+        let tracks = to_intelligence_tracks(json.to_string()).unwrap();
+        assert!(!tracks[0].recommended);
+        assert_eq!(tracks[0].alg, "");
+        assert!(tracks[1].recommended);
+        assert_eq!(tracks[1].alg, "hb_rt_sim");
+        assert_eq!(tracks[1].song.name, "稻香");
+    }
+
+    #[test]
+    fn to_homepage_parses_blocks() {
+        let json = r#"{"code":200,"data":{"cursor":"{\"offset\":6}","hasMore":true,"blocks":[
+            {"blockCode":"HOMEPAGE_BANNER","showType":"BANNER","extInfo":{"banners":[
+                {"pic":"http://b","targetId":186016,"targetType":1,"typeTitle":"独家","url":null},
+                {"targetType":1}]}},
+            {"showType":"BROKEN"},
+            {"blockCode":"HOMEPAGE_BLOCK_PLAYLIST_RCMD","showType":"HOMEPAGE_SLIDE_PLAYLIST",
+             "uiElement":{"subTitle":{"title":"推荐歌单"}},
+             "creatives":[{"creativeType":"list","resources":[
+                {"uiElement":{"mainTitle":{"title":"华语"},"image":{"imageUrl":"http://p"}},
+                 "resourceType":"list","resourceId":"2829883282"},
+                {"resourceType":"list","resourceId":"1"}]}]},
+            {"blockCode":"HOMEPAGE_BLOCK_NEW_ALBUM_NEW_SONG","showType":"HOMEPAGE_NEW_SONG_NEW_ALBUM",
+             "creatives":[
+                {"creativeType":"NEW_SONG_HOMEPAGE","resources":[
+                    {"uiElement":{"mainTitle":{"title":"晴天"}},"resourceType":"song","resourceId":"186016",
+                     "resourceExtInfo":{"artists":[{"name":"周杰伦"}]}}]},
+                {"creativeType":"NEW_ALBUM_HOMEPAGE","resources":[
+                    {"uiElement":{"mainTitle":{"title":"叶惠美"}},"resourceType":"album","resourceId":"18905"}]}]},
+            {"blockCode":"HOMEPAGE_BLOCK_TOPLIST","showType":"HOMEPAGE_SLIDE_PLAYABLE_LIST",
+             "creatives":[{"creativeId":"19723756","uiElement":{"mainTitle":{"title":"飙升榜"}},"resources":[]}]},
+            {"blockCode":"HOMEPAGE_MUSIC_CALENDAR","showType":"SHUFFLE_MUSIC_CALENDAR"}]}}"#;
+        let page = to_homepage(json.to_string()).unwrap();
+        assert_eq!(page.cursor.as_deref(), Some(r#"{"offset":6}"#));
+        assert!(page.has_more);
+        assert!(
+            matches!(&page.blocks[0], HomepageBlock::Banner(b) if b.len() == 1 && b[0].target_id == 186016)
+        );
+        match &page.blocks[1] {
+            HomepageBlock::Playlists { title, items } => {
+                assert_eq!(title, "推荐歌单");
+                assert_eq!(items.len(), 1);
+                assert_eq!(items[0].id, "2829883282");
+            }
+            block => panic!("unexpected block: {:?}", block),
+        }
+        match &page.blocks[2] {
+            HomepageBlock::NewSongsAlbums { songs, albums, .. } => {
+                assert_eq!(songs[0].subtitle, "周杰伦");
+                assert_eq!(albums[0].title, "叶惠美");
+            }
+            block => panic!("unexpected block: {:?}", block),
+        }
+        assert!(
+            matches!(&page.blocks[3], HomepageBlock::Toplist { lists, .. } if lists[0].name == "飙升榜")
+        );
+        assert!(matches!(&page.blocks[4], HomepageBlock::Other { .. }));
+    }
+
+    #[test]
+    fn to_banners_info_keeps_payloads() {
+        let json = r#"{"code":200,"banners":[
+            {"pic":"http://a","targetId":0,"targetType":3000,"typeTitle":"活动","url":"https://music.163.com/st/a","encodeId":"0"},
+            {"pic":"http://b","targetId":0,"targetType":1014,"typeTitle":"视频","url":null,"encodeId":"89ADDE33C0AAE8EC14B99F6750DB954D"},
+            {"imageUrl":"http://c","targetId":2829883282,"targetType":1000,"typeTitle":"歌单"},
+            {"imageUrl":"http://d","targetId":1,"targetType":1005,"typeTitle":""},
+            {"imageUrl":"http://e","targetId":2498420,"targetType":1001,"typeTitle":"电台节目"}]}"#;
+        let banners = to_banners_info(json.to_string()).unwrap();
+        assert_eq!(
+            banners[0].target_type,
+            TargetType::Url("https://music.163.com/st/a".to_owned())
+        );
+        assert_eq!(
+            banners[1].target_type,
+            TargetType::Video("89ADDE33C0AAE8EC14B99F6750DB954D".to_owned())
+        );
+        assert_eq!(banners[2].target_type, TargetType::SongList);
+        assert_eq!(banners[2].type_title, "歌单");
+        assert_eq!(banners[3].target_type, TargetType::Unknown(1005));
+        assert_eq!(banners[4].target_type, TargetType::Program);
+        assert_eq!(banners[4].target_id, 2498420);
+    }
+
+    #[test]
+    fn to_ranked_songs_reports_changes() {
+        let json: Value = serde_json::from_str(
+            r#"{"code":200,"playlist":{"id":19723756,"name":"飙升榜","coverImgUrl":"","createTime":0,"trackUpdateTime":0,
+            "trackIds":[{"id":1,"lr":3},{"id":2},{"id":3,"lr":0}],
+            "tracks":[
+                {"id":1,"name":"a","ar":[{"name":"x"}],"al":{"id":1,"name":"x"},"dt":1},
+                {"id":2,"name":"b","ar":[{"name":"x"}],"al":{"id":1,"name":"x"},"dt":1},
+                {"id":3,"name":"c","ar":[{"name":"x"}],"al":{"id":1,"name":"x"},"dt":1}]},
+            "privileges":[{"id":1,"st":0,"fee":8,"pl":128000},{"id":2,"st":0,"fee":8,"pl":128000},{"id":3,"st":0,"fee":8,"pl":128000}]}"#,
+        )
+        .unwrap();
+        let songs = to_ranked_songs(&json).unwrap();
+        assert_eq!(songs[0].change(), RankChange::Up(3));
+        assert_eq!(songs[1].change(), RankChange::New);
+        assert_eq!(songs[2].change(), RankChange::Down(2));
+
+        let json = r#"{"code":200,"list":[
+            {"id":19723756,"name":"飙升榜","ToplistType":"S","updateFrequency":"每天更新",
+             "tracks":[{"first":"晴天","second":"周杰伦"},{"first":"稻香","second":"周杰伦"}]},
+            {"id":60198,"name":"美国Billboard榜","ToplistType":null,"tracks":[]},
+            {"id":71384707,"name":"古典榜","tracks":[]},
+            {"id":5059644681,"name":"UKG榜","tracks":[]}]}"#;
+        let toplists = to_toplist_detail(json.to_string()).unwrap();
+        assert_eq!(toplists[0].category, ToplistCategory::Official);
+        assert_eq!(
+            toplists[0].preview[1],
+            ("稻香".to_owned(), "周杰伦".to_owned())
+        );
+        assert_eq!(toplists[1].category, ToplistCategory::Global);
+        assert_eq!(toplists[2].category, ToplistCategory::Genre);
+        assert_eq!(toplists[3].category, ToplistCategory::Genre);
+    }
+
+    #[test]
+    fn to_playlist_catalogue_groups_tags() {
+        let json = r#"{"code":200,"all":{"name":"全部歌单","category":4,"hot":false},
+            "sub":[{"name":"华语","category":0,"hot":true,"activity":false},
+                   {"name":"摇滚","category":1,"hot":false,"activity":false},
+                   {"name":"粤语","category":0,"hot":false,"activity":false}],
+            "categories":{"1":"风格","0":"语种","2":"场景","3":"情感","4":"主题"}}"#;
+        let catalogue = to_playlist_catalogue(json.to_string()).unwrap();
+        assert_eq!(catalogue.all, "全部歌单");
+        assert_eq!(catalogue.groups.len(), 5);
+        assert_eq!(catalogue.groups[0].kind, CatalogueKind::Language);
+        let names: Vec<&str> = catalogue.groups[0]
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["华语", "粤语"]);
+        assert!(catalogue.groups[0].tags[0].hot);
+        assert_eq!(catalogue.groups[1].tags[0].name, "摇滚");
+
+        let json = r#"{"code":200,"tags":[{"playlistTag":{"id":5001,"name":"华语","category":0},"hot":false,"name":"华语","id":5001}]}"#;
+        let tags = to_playlist_hot_tags(json.to_string()).unwrap();
+        assert_eq!(tags[0].id, 5001);
+    }
+
+    #[test]
+    fn to_mix_detail_parses_header() {
+        let json = r#"{"code":200,"playlist":{"id":7,"name":"p","coverImgUrl":"c","createTime":1,
+            "trackUpdateTime":2,"trackCount":1,"playCount":300,"subscribedCount":20,"privacy":10,
+            "specialType":5,"subscribed":null,"tags":["华语","流行"],
+            "creator":{"userId":9,"nickname":"n","avatarUrl":"a"},
+            "tracks":[{"id":1,"name":"a","ar":[{"name":"s"}],"al":{"id":2,"name":"x"},"dt":1}]},
+            "privileges":[{"id":1,"fee":8,"st":0,"pl":128000,"dl":0,"maxbr":999000}]}"#;
+        let detail = to_mix_detail(&serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(detail.creator.uid, 9);
+        assert_eq!(detail.tags, vec!["华语", "流行"]);
+        assert_eq!(detail.play_count, 300);
+        assert_eq!(detail.privacy, 10);
+        assert_eq!(detail.special_type, 5);
+        assert!(!detail.subscribed);
+        assert_eq!(detail.songs.len(), 1);
+    }
+
+    #[test]
+    fn play_list_detail_reads_legacy_cache() {
+        let json = r#"{"id":7,"name":"p","cover_img_url":"c","description":"",
+            "create_time":1,"track_update_time":2,"songs":[]}"#;
+        let detail: PlayListDetail = serde_json::from_str(json).unwrap();
+        assert_eq!(detail.creator.uid, 0);
+        assert!(detail.tags.is_empty());
+        assert!(!detail.subscribed);
+    }
+
+    #[test]
+    fn to_album_detail_keeps_disc_layout() {
+        let json = r#"{"code":200,"album":{"id":3,"name":"x","picUrl":"p","publishTime":1,
+            "artist":{"id":1,"name":"a","picUrl":""},
+            "artists":[{"id":1,"name":"a","picUrl":""},{"id":2,"name":"b","picUrl":""}],
+            "company":"c","type":"EP/Single","subType":"现场版","size":2,"alias":["y"],
+            "onSale":true,"paid":false},
+            "songs":[
+              {"id":10,"name":"s1","ar":[{"name":"a"}],"al":{"id":3,"picUrl":"q"},"dt":1,"cd":"01","no":1,
+               "privilege":{"id":10,"fee":8,"st":0,"pl":128000,"dl":0,"maxbr":999000}},
+              {"id":11,"name":"s2","ar":[{"name":"a"}],"al":{"id":3},"dt":1,"cd":"02","no":1,
+               "privilege":{"id":11,"fee":8,"st":0,"pl":128000,"dl":0,"maxbr":999000}}]}"#;
+        let album = to_album_detail(&serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(album.artists.len(), 2);
+        assert_eq!(album.sub_type, "现场版");
+        assert!(album.on_sale);
+        assert_eq!(album.songs[0].pic_url, "q");
+        assert_eq!(album.songs[1].pic_url, "p");
+        assert_eq!((album.songs[1].disc, album.songs[1].no), (2, 1));
+        assert_eq!(album.position(11), Some((2, 1)));
+        assert_eq!(album.position(12), None);
+    }
+
     #[test]
     fn to_song_list_parses_special_type() {
         let json = r#"{"code":200,"playlist":[
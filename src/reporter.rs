@@ -0,0 +1,152 @@
+//
+// reporter.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+use crate::model::{PlayEnd, PlayEvent};
+use crate::MusicApi;
+use anyhow::Result;
+use std::time::{Duration, Instant};
+
+/// 默认重试次数
+const RETRIES: u32 = 3;
+/// 首次失败后的等待时间
+const BACKOFF: Duration = Duration::from_secs(2);
+/// 最长等待时间
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// 播放记录上报
+/// 缓存播放开始/结束事件，调用 flush 时批量上报
+pub struct PlaybackReporter {
+    api: MusicApi,
+    retries: u32,
+    pending: Vec<PlayEvent>,
+    current: Option<(u64, u64, Instant)>,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl PlaybackReporter {
+    pub fn new(api: MusicApi) -> Self {
+        Self {
+            api,
+            retries: RETRIES,
+            pending: Vec::new(),
+            current: None,
+            failures: 0,
+            retry_at: None,
+        }
+    }
+
+    /// 设置上报连续失败多少次后丢弃待上报事件
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// 开始播放
+    /// 若上一首未结束，按已播放时长记为切歌
+    /// song_id: 歌曲 id
+    /// source_id: 来源 id (歌单、专辑等)
+    pub fn start(&mut self, song_id: u64, source_id: u64) {
+        if let Some((_, _, started)) = self.current {
+            self.end(started.elapsed().as_secs(), PlayEnd::Ui);
+        }
+        self.pending.push(PlayEvent::Start { song_id, source_id });
+        self.current = Some((song_id, source_id, Instant::now()));
+    }
+
+    /// 结束播放
+    /// played_seconds: 实际播放时长(秒)
+    /// end: 结束方式
+    pub fn end(&mut self, played_seconds: u64, end: PlayEnd) {
+        if let Some((song_id, source_id, _)) = self.current.take() {
+            self.pending.push(PlayEvent::End {
+                song_id,
+                source_id,
+                played_seconds,
+                end,
+            });
+        }
+    }
+
+    /// 待上报事件数
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// 上报所有待上报事件
+    /// 失败时事件保留，等待时间按失败次数倍增 (最长 5 分钟)，等待期间调用直接返回
+    /// 连续失败超过重试次数后丢弃事件
+    pub async fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        if self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return Ok(());
+        }
+        let result = self.api.report_play_events(&self.pending).await;
+        if result.is_ok() {
+            self.pending.clear();
+            self.failures = 0;
+            self.retry_at = None;
+        } else {
+            self.failures += 1;
+            if self.failures > self.retries {
+                self.pending.clear();
+                self.failures = 0;
+                self.retry_at = None;
+            } else {
+                self.retry_at = Some(Instant::now() + backoff(self.failures));
+            }
+        }
+        result
+    }
+}
+
+/// 第 failures 次失败后的等待时间
+fn backoff(failures: u32) -> Duration {
+    BACKOFF
+        .saturating_mul(1 << failures.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_ends_previous_song() {
+        let mut reporter = PlaybackReporter::new(MusicApi::default());
+        reporter.start(1, 100);
+        reporter.start(2, 100);
+        reporter.end(180, PlayEnd::PlayEnd);
+        assert_eq!(reporter.pending(), 4);
+        assert!(matches!(
+            reporter.pending[1],
+            PlayEvent::End {
+                song_id: 1,
+                end: PlayEnd::Ui,
+                ..
+            }
+        ));
+        assert_eq!(
+            reporter.pending[3],
+            PlayEvent::End {
+                song_id: 2,
+                source_id: 100,
+                played_seconds: 180,
+                end: PlayEnd::PlayEnd,
+            }
+        );
+        reporter.end(10, PlayEnd::Interrupt);
+        assert_eq!(reporter.pending(), 4);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(20), MAX_BACKOFF);
+    }
+}
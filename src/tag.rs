@@ -0,0 +1,281 @@
+//
+// tag.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+use crate::date::year_from_timestamp;
+use crate::model::{AlbumDetail, Lyrics, SongInfo};
+use anyhow::{anyhow, Result};
+use lofty::{
+    config::WriteOptions,
+    picture::{Picture, PictureType},
+    prelude::*,
+    probe::Probe,
+    tag::{items::Timestamp, ItemValue, Tag, TagItem, TagType},
+};
+use std::path::Path;
+
+/// 歌曲标签
+#[derive(Debug, Clone, Default)]
+pub struct SongTag {
+    /// 歌名
+    pub title: String,
+    /// 歌手
+    pub artists: Vec<String>,
+    /// 专辑
+    pub album: String,
+    /// 专辑歌手
+    pub album_artist: Option<String>,
+    /// 音轨号
+    pub track: Option<u32>,
+    /// 碟片号
+    pub disc: Option<u32>,
+    /// 发行年份
+    pub year: Option<u16>,
+    /// 封面图片数据
+    pub cover: Option<Vec<u8>>,
+    /// 歌词
+    pub lyrics: Option<String>,
+}
+
+impl SongTag {
+    /// 由歌曲信息生成标签
+    pub fn from_song(song: &SongInfo) -> Self {
+        Self {
+            track: Some(song.no).filter(|n| *n > 0),
+            disc: Some(song.disc).filter(|d| *d > 0),
+            title: song.name.clone(),
+            artists: if song.artists.is_empty() {
+                vec![song.singer.clone()]
+            } else {
+                song.artists.clone()
+            },
+            album: song.album.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// 补充专辑信息 (专辑歌手、发行年份、碟片号、音轨号)
+    pub fn with_album(mut self, album: &AlbumDetail, song_id: u64) -> Self {
+        self.album = album.name.clone();
+        self.album_artist = Some(album.artist_name.clone());
+        self.year = Some(year_from_timestamp(album.publish_time));
+        if let Some((disc, no)) = album.position(song_id) {
+            self.disc = Some(disc);
+            self.track = Some(no);
+        }
+        self
+    }
+
+    /// 补充歌词
+    pub fn with_lyrics(mut self, lyrics: &Lyrics) -> Self {
+        if !lyrics.lyric.is_empty() {
+            self.lyrics = Some(lyrics.lyric.join("\n"));
+        }
+        self
+    }
+
+    /// 从音乐文件读取标签
+    /// 返回标签及码率
+    /// path: 音乐文件路径
+    pub fn read_from(path: &Path) -> Result<(Self, u32)> {
+        let tagged = lofty::read_from_path(path)?;
+        let bitrate = tagged.properties().audio_bitrate().unwrap_or_default() * 1000;
+        let mut tag = Self::default();
+        if let Some(t) = tagged.primary_tag().or_else(|| tagged.first_tag()) {
+            tag.title = t.title().unwrap_or_default().into_owned();
+            tag.artists = t
+                .get_strings(ItemKey::TrackArtist)
+                .map(str::to_owned)
+                .collect();
+            tag.album = t.album().unwrap_or_default().into_owned();
+            tag.album_artist = t.get_string(ItemKey::AlbumArtist).map(str::to_owned);
+            tag.track = t.track();
+            tag.disc = t.disk();
+            tag.year = t.date().map(|d| d.year);
+        }
+        Ok((tag, bitrate))
+    }
+
+    /// 写入标签
+    /// MP3 写入 ID3v2.4, FLAC 写入 Vorbis comments 及 picture block, M4A 写入 ilst atoms
+    /// path: 音乐文件路径
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let file_type = Probe::open(path)?
+            .guess_file_type()?
+            .file_type()
+            .ok_or_else(|| anyhow!("unknown audio format: {}", path.display()))?;
+        let mut tag = Tag::new(file_type.primary_tag_type());
+
+        tag.set_title(self.title.clone());
+        for artist in self.artists.iter().filter(|a| !a.is_empty()) {
+            tag.push(TagItem::new(
+                ItemKey::TrackArtist,
+                ItemValue::Text(artist.clone()),
+            ));
+        }
+        tag.set_album(self.album.clone());
+        if let Some(album_artist) = &self.album_artist {
+            tag.insert_text(ItemKey::AlbumArtist, album_artist.clone());
+        }
+        if let Some(track) = self.track {
+            tag.set_track(track);
+        }
+        if let Some(disc) = self.disc {
+            tag.set_disk(disc);
+        }
+        if let Some(year) = self.year {
+            // ID3v2 写入 TDRC, FLAC 写入 DATE, M4A 写入 ©day
+            tag.set_date(Timestamp {
+                year,
+                ..Default::default()
+            });
+        }
+        if let Some(cover) = &self.cover {
+            let mut picture = Picture::from_reader(&mut &cover[..])?;
+            picture.set_pic_type(PictureType::CoverFront);
+            tag.push_picture(picture);
+        }
+        if let Some(lyrics) = &self.lyrics {
+            // ID3v2 的歌词写入 USLT 帧，其他格式使用 LYRICS 字段
+            let key = if tag.tag_type() == TagType::Id3v2 {
+                ItemKey::UnsyncLyrics
+            } else {
+                ItemKey::Lyrics
+            };
+            tag.insert_text(key, lyrics.clone());
+        }
+
+        tag.save_to_path(path, WriteOptions::default())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_song_keeps_all_artists() {
+        let song = SongInfo {
+            singer: "周杰伦".to_owned(),
+            artists: vec!["周杰伦".to_owned(), "费玉清".to_owned()],
+            ..Default::default()
+        };
+        assert_eq!(SongTag::from_song(&song).artists, song.artists);
+    }
+
+    #[test]
+    fn write_flac_tag() {
+        let mut flac = b"fLaC".to_vec();
+        // STREAMINFO, last metadata block
+        flac.extend_from_slice(&[0x80, 0x00, 0x00, 0x22]);
+        flac.extend_from_slice(&[0x10, 0x00, 0x10, 0x00]);
+        flac.extend_from_slice(&[0x00; 6]);
+        // 44100Hz, 2 channels, 16 bits
+        flac.extend_from_slice(&[0x0a, 0xc4, 0x42, 0xf0]);
+        flac.extend_from_slice(&[0x00; 4]);
+        flac.extend_from_slice(&[0x00; 16]);
+
+        let path = std::env::temp_dir().join("netease-cloud-music-api-tag-test.flac");
+        std::fs::write(&path, flac).unwrap();
+        let tag = SongTag {
+            title: "晴天".to_owned(),
+            artists: vec!["周杰伦".to_owned()],
+            album: "叶惠美".to_owned(),
+            track: Some(3),
+            year: Some(2003),
+            lyrics: Some("[00:00.00]故事的小黄花".to_owned()),
+            ..Default::default()
+        };
+        tag.write_to(&path).unwrap();
+
+        let tagged = lofty::read_from_path(&path).unwrap();
+        let written = tagged.primary_tag().unwrap();
+        assert_eq!(written.title().as_deref(), Some("晴天"));
+        assert_eq!(written.artist().as_deref(), Some("周杰伦"));
+        assert_eq!(written.track(), Some(3));
+        assert_eq!(written.get_string(ItemKey::RecordingDate), Some("2003"));
+        assert_eq!(
+            written.get_string(ItemKey::Lyrics),
+            Some("[00:00.00]故事的小黄花")
+        );
+        let (read, _) = SongTag::read_from(&path).unwrap();
+        assert_eq!(read.artists, vec!["周杰伦".to_owned()]);
+        assert_eq!(read.album, "叶惠美");
+        assert_eq!(read.year, Some(2003));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// 1x1 PNG
+    const COVER: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f,
+        0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    fn lyric_tag() -> SongTag {
+        SongTag {
+            title: "晴天".to_owned(),
+            artists: vec!["周杰伦".to_owned()],
+            lyrics: Some("故事的小黄花".to_owned()),
+            cover: Some(COVER.to_vec()),
+            year: Some(2003),
+            ..Default::default()
+        }
+    }
+
+    fn round_trip(name: &str, data: Vec<u8>) -> Tag {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, data).unwrap();
+        lyric_tag().write_to(&path).unwrap();
+        let tagged = Probe::open(&path)
+            .unwrap()
+            .options(lofty::config::ParseOptions::new().read_properties(false))
+            .read()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        tagged.primary_tag().unwrap().clone()
+    }
+
+    #[test]
+    fn write_mp3_tag() {
+        // MPEG-1 Layer III, 128kbps, 44100Hz
+        let mut mp3 = vec![];
+        for _ in 0..4 {
+            mp3.extend_from_slice(&[0xff, 0xfb, 0x90, 0x64]);
+            mp3.extend_from_slice(&[0x00; 413]);
+        }
+        let written = round_trip("netease-cloud-music-api-tag-test.mp3", mp3);
+        assert_eq!(written.tag_type(), TagType::Id3v2);
+        assert_eq!(written.date().map(|d| d.year), Some(2003));
+        assert_eq!(
+            written.get_string(ItemKey::UnsyncLyrics),
+            Some("故事的小黄花")
+        );
+        assert_eq!(written.pictures()[0].data(), COVER);
+    }
+
+    #[test]
+    fn write_m4a_tag() {
+        fn atom(name: &[u8; 4], body: &[u8]) -> Vec<u8> {
+            let mut a = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+            a.extend_from_slice(name);
+            a.extend_from_slice(body);
+            a
+        }
+        let mut mvhd = vec![0u8; 100];
+        // timescale 1000
+        mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+        let mut m4a = atom(b"ftyp", b"M4A \0\0\0\0M4A mp42isom");
+        m4a.extend(atom(b"moov", &atom(b"mvhd", &mvhd)));
+        m4a.extend(atom(b"mdat", &[]));
+        let written = round_trip("netease-cloud-music-api-tag-test.m4a", m4a);
+        assert_eq!(written.tag_type(), TagType::Mp4Ilst);
+        assert_eq!(written.date().map(|d| d.year), Some(2003));
+        assert_eq!(written.get_string(ItemKey::Lyrics), Some("故事的小黄花"));
+        assert_eq!(written.pictures()[0].data(), COVER);
+    }
+}
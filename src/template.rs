@@ -0,0 +1,322 @@
+//
+// template.rs
+// Copyright (C) 2019 gmg137 <gmg137@live.com>
+// Distributed under terms of the GPLv3 license.
+//
+use crate::date::year_from_timestamp;
+use crate::model::{AlbumDetail, SongInfo, SongQuality, SongUrl};
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+
+/// 文件名默认最大长度 (字节)
+const MAX_NAME_LEN: usize = 200;
+
+/// 文件名模板
+/// 以 / 分隔目录，支持的字段:
+///   {title}: 歌名
+///   {artist}: 歌手
+///   {album}: 专辑
+///   {album_artist}: 专辑歌手
+///   {track}: 音轨号
+///   {disc}: 碟片号
+///   {year}: 发行年份
+///   {id}: 歌曲 id
+///   {ext}: 扩展名 (mp3/flac)
+/// 数字字段支持补零, 如 {track:02}
+#[derive(Debug, Clone)]
+pub struct FileNameTemplate {
+    template: String,
+    max_len: usize,
+}
+
+impl FileNameTemplate {
+    /// template: 模板, 如 "{artist}/{album}/{track:02} - {title}.{ext}"
+    pub fn new<S: Into<String>>(template: S) -> Self {
+        Self {
+            template: template.into(),
+            max_len: MAX_NAME_LEN,
+        }
+    }
+
+    /// 设置每级文件名的最大长度 (字节)
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// 生成相对路径
+    /// song: 歌曲信息
+    /// album: 专辑详情，用于专辑歌手、音轨号及年份
+    /// url: 歌曲 URL，用于确定扩展名
+    pub fn render(
+        &self,
+        song: &SongInfo,
+        album: Option<&AlbumDetail>,
+        url: &SongUrl,
+    ) -> Result<PathBuf> {
+        let mut path = PathBuf::new();
+        for part in self.template.split('/').filter(|p| !p.is_empty()) {
+            let name = self.render_part(part, song, album, url)?;
+            path.push(truncate_name(&sanitize(&name), self.max_len));
+        }
+        if path.as_os_str().is_empty() {
+            return Err(anyhow!("empty file name template"));
+        }
+        Ok(path)
+    }
+
+    fn render_part(
+        &self,
+        part: &str,
+        song: &SongInfo,
+        album: Option<&AlbumDetail>,
+        url: &SongUrl,
+    ) -> Result<String> {
+        let mut out = String::new();
+        let mut rest = part;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| anyhow!("unclosed '{{' in template: {}", self.template))?
+                + start;
+            let (field, width) = match rest[start + 1..end].split_once(':') {
+                Some((field, width)) => (field, Some(width)),
+                None => (&rest[start + 1..end], None),
+            };
+            let value = match field {
+                "title" => Field::Text(song.name.clone()),
+                "artist" => Field::Text(song.singer.clone()),
+                "album" => {
+                    Field::Text(album.map_or_else(|| song.album.clone(), |a| a.name.clone()))
+                }
+                "album_artist" => Field::Text(
+                    album.map_or_else(|| song.singer.clone(), |a| a.artist_name.clone()),
+                ),
+                // 无专辑详情时使用歌曲自带的曲目号和碟片号
+                "track" => Field::Number(
+                    album
+                        .and_then(|a| a.position(song.id))
+                        .map_or(u64::from(song.no), |(_, no)| u64::from(no)),
+                ),
+                "disc" => Field::Number(
+                    album
+                        .and_then(|a| a.position(song.id))
+                        .map_or(u64::from(song.disc.max(1)), |(disc, _)| u64::from(disc)),
+                ),
+                "year" => Field::Number(
+                    album.map_or(0, |a| u64::from(year_from_timestamp(a.publish_time))),
+                ),
+                "id" => Field::Number(song.id),
+                "ext" => Field::Text(extension(url).to_owned()),
+                _ => return Err(anyhow!("unknown template field: {}", field)),
+            };
+            let value = match (value, width) {
+                (Field::Text(s), _) => escape(&s),
+                (Field::Number(n), Some(width)) => {
+                    let width: usize = width
+                        .parse()
+                        .map_err(|_| anyhow!("invalid width '{}' in template", width))?;
+                    format!("{:0width$}", n, width = width)
+                }
+                (Field::Number(n), None) => n.to_string(),
+            };
+            out.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+enum Field {
+    Text(String),
+    Number(u64),
+}
+
+/// 根据 URL 确定扩展名
+pub(crate) fn extension(url: &SongUrl) -> &'static str {
+    match url.file_type.to_ascii_lowercase().as_str() {
+        "flac" => return "flac",
+        "mp3" => return "mp3",
+        "m4a" | "aac" => return "m4a",
+        _ => {}
+    }
+    let path = url.url.split('?').next().unwrap_or_default();
+    match path
+        .rsplit('.')
+        .next()
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("flac") => "flac",
+        Some("mp3") => "mp3",
+        Some("m4a") => "m4a",
+        _ if url.quality >= SongQuality::Lossless => "flac",
+        _ => "mp3",
+    }
+}
+
+/// 字段中的目录分隔符不应产生新目录
+fn escape(s: &str) -> String {
+    s.replace(['/', '\\'], "_")
+}
+
+/// 替换文件系统不允许的字符
+fn sanitize(name: &str) -> String {
+    let mut name: String = name
+        .chars()
+        .map(|c| match c {
+            '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    name = name.trim().trim_end_matches('.').to_owned();
+    let stem = name
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem.as_bytes()[3].is_ascii_digit());
+    if name.is_empty() || name == ".." || reserved {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// 按字节截断文件名，保留扩展名
+fn truncate_name(name: &str, max_len: usize) -> String {
+    if name.len() <= max_len {
+        return name.to_owned();
+    }
+    let (stem, ext) = match name.rfind('.') {
+        Some(i) if i > 0 && name.len() - i <= 6 => (&name[..i], &name[i..]),
+        _ => (name, ""),
+    };
+    let mut end = max_len.saturating_sub(ext.len());
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", stem[..end].trim_end(), ext)
+}
+
+/// 文件已存在时添加序号, 如 "晴天 (1).mp3"
+pub fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|i| path.with_file_name(format!("{} ({}){}", stem, i, ext)))
+        .find(|p| !p.exists())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: u64, name: &str) -> SongInfo {
+        SongInfo {
+            id,
+            name: name.to_owned(),
+            singer: "AC/DC".to_owned(),
+            album: "Back in Black".to_owned(),
+            album_id: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_template() {
+        let url = SongUrl {
+            id: 2,
+            url: "http://m701.music.126.net/a/b/c.flac?vuutv=x".to_owned(),
+            rate: 999000,
+            quality: SongQuality::Lossless,
+            ..Default::default()
+        };
+        let album = AlbumDetail {
+            id: 1,
+            name: "Back in Black".to_owned(),
+            pic_url: String::new(),
+            description: String::new(),
+            publish_time: 333907200000,
+            artist_id: 1,
+            artist_name: "AC/DC".to_owned(),
+            artist_pic_url: String::new(),
+            artists: vec![],
+            company: String::new(),
+            album_type: String::new(),
+            sub_type: String::new(),
+            size: 2,
+            alias: vec![],
+            on_sale: false,
+            paid: false,
+            songs: vec![song(1, "Hells Bells"), song(2, "What: Do You Do?")],
+        };
+        let template =
+            FileNameTemplate::new("{artist}/{year} - {album}/{track:02} - {title}.{ext}");
+        let path = template
+            .render(&album.songs[1], Some(&album), &url)
+            .unwrap();
+        assert_eq!(
+            path,
+            PathBuf::from("AC_DC/1980 - Back in Black/02 - What_ Do You Do_.flac")
+        );
+
+        let path = FileNameTemplate::new("{title}.{ext}")
+            .max_len(12)
+            .render(&song(3, "七里香七里香"), None, &url)
+            .unwrap();
+        assert_eq!(path, PathBuf::from("七里.flac"));
+
+        assert!(FileNameTemplate::new("{name}")
+            .render(&song(3, "x"), None, &url)
+            .is_err());
+
+        let single = SongInfo {
+            disc: 2,
+            no: 7,
+            ..song(4, "Shoot to Thrill")
+        };
+        let path = FileNameTemplate::new("{disc}-{track:02} {title}.{ext}")
+            .render(&single, None, &url)
+            .unwrap();
+        assert_eq!(path, PathBuf::from("2-07 Shoot to Thrill.flac"));
+    }
+
+    #[test]
+    fn sanitize_reserved_names() {
+        assert_eq!(sanitize("CON"), "_CON");
+        assert_eq!(sanitize("com1.mp3"), "_com1.mp3");
+        assert_eq!(sanitize("COM10"), "COM10");
+        assert_eq!(sanitize("a?b. "), "a_b");
+        assert_eq!(sanitize(".."), "_");
+    }
+
+    #[test]
+    fn unique_path_appends_counter() {
+        let dir = std::env::temp_dir().join("netease-cloud-music-api-unique-path-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.mp3");
+        assert_eq!(unique_path(&path), path);
+        std::fs::write(&path, b"").unwrap();
+        assert_eq!(unique_path(&path), dir.join("a (1).mp3"));
+        std::fs::write(dir.join("a (1).mp3"), b"").unwrap();
+        assert_eq!(unique_path(&path), dir.join("a (2).mp3"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}